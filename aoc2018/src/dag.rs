@@ -0,0 +1,378 @@
+use failure::{bail, Error};
+use hashbrown::HashMap;
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+/// A graph of tasks, where an edge from `before` to `after` means that `before` must be finished
+/// before `after` can start.
+///
+/// Tasks are identified by keys of any type, and are kept in the order they were first added.
+#[derive(Debug, Clone)]
+pub struct Dag<K>
+where
+    K: Eq + Hash,
+{
+    keys: Vec<K>,
+    indices: HashMap<K, usize>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl<K> Dag<K>
+where
+    K: Clone + Eq + Hash + Display,
+{
+    pub fn new() -> Dag<K> {
+        Dag {
+            keys: Vec::new(),
+            indices: HashMap::new(),
+            successors: Vec::new(),
+        }
+    }
+
+    /// Add a task without dependencies, returning its index. Adding a task again does nothing.
+    pub fn add_node(&mut self, key: K) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index;
+        }
+        let index = self.keys.len();
+        self.keys.push(key.clone());
+        self.indices.insert(key, index);
+        self.successors.push(Vec::new());
+        index
+    }
+
+    /// Add a dependency, adding the tasks if they are new.
+    pub fn add_edge(&mut self, before: K, after: K) {
+        let before = self.add_node(before);
+        let after = self.add_node(after);
+        self.successors[before].push(after);
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The tasks in the order they were added.
+    pub fn nodes(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// A cycle of dependencies, starting and ending with the same task, if there is one.
+    pub fn find_cycle(&self) -> Option<Vec<K>> {
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        enum Mark {
+            New,
+            Active,
+            Done,
+        }
+
+        let mut marks = vec![Mark::New; self.len()];
+        for root in 0..self.len() {
+            if marks[root] != Mark::New {
+                continue;
+            }
+            // The path from the root, with the index of the next successor to visit for each task.
+            let mut path = vec![(root, 0)];
+            marks[root] = Mark::Active;
+            while let Some(top) = path.last_mut() {
+                let node = top.0;
+                if let Some(&successor) = self.successors[node].get(top.1) {
+                    top.1 += 1;
+                    match marks[successor] {
+                        Mark::New => {
+                            marks[successor] = Mark::Active;
+                            path.push((successor, 0));
+                        }
+                        Mark::Active => {
+                            let start = path.iter().position(|&(n, _)| n == successor).unwrap();
+                            let mut cycle = path[start..]
+                                .iter()
+                                .map(|&(n, _)| self.keys[n].clone())
+                                .collect::<Vec<_>>();
+                            cycle.push(self.keys[successor].clone());
+                            return Some(cycle);
+                        }
+                        Mark::Done => {}
+                    }
+                } else {
+                    marks[node] = Mark::Done;
+                    path.pop();
+                }
+            }
+        }
+        None
+    }
+
+    fn check_acyclic(&self) -> Result<(), Error> {
+        if let Some(cycle) = self.find_cycle() {
+            bail!("Dependency cycle {}", cycle.iter().join(" -> "));
+        }
+        Ok(())
+    }
+
+    /// The number of unfinished dependencies of each task.
+    fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degrees = vec![0; self.len()];
+        for &successor in self.successors.iter().flatten() {
+            in_degrees[successor] += 1;
+        }
+        in_degrees
+    }
+
+    /// All tasks in an order respecting the dependencies, where the ready task with the smallest
+    /// priority is always taken next.
+    pub fn order_by<P, F>(&self, priority: F) -> Result<Vec<K>, Error>
+    where
+        P: Ord,
+        F: Fn(&K) -> P,
+    {
+        Ok(self
+            .schedule(1, priority, |_| 0)?
+            .assignments
+            .into_iter()
+            .map(|assignment| assignment.key)
+            .collect())
+    }
+
+    /// Simulate `workers` working on the tasks, each task taking `duration` time units.
+    ///
+    /// Whenever a worker is idle it starts on the ready task with the smallest priority. At each
+    /// point in time, tasks that finish are handled before idle workers pick new tasks, and idle
+    /// workers are assigned in order of their number.
+    pub fn schedule<P, F, D>(
+        &self,
+        workers: usize,
+        priority: F,
+        duration: D,
+    ) -> Result<Schedule<K>, Error>
+    where
+        P: Ord,
+        F: Fn(&K) -> P,
+        D: Fn(&K) -> u64,
+    {
+        if workers == 0 {
+            bail!("At least one worker is needed");
+        }
+        self.check_acyclic()?;
+
+        let mut in_degrees = self.in_degrees();
+        let mut ready = BinaryHeap::new();
+        for (index, &in_degree) in in_degrees.iter().enumerate() {
+            if in_degree == 0 {
+                ready.push(Reverse((priority(&self.keys[index]), index)));
+            }
+        }
+
+        let mut assignments = Vec::with_capacity(self.len());
+        let mut idle = (0..workers).map(Reverse).collect::<BinaryHeap<_>>();
+        let mut running = BinaryHeap::new();
+        let mut time = 0;
+        loop {
+            while !idle.is_empty() && !ready.is_empty() {
+                let Reverse(worker) = idle.pop().unwrap();
+                let Reverse((_, index)) = ready.pop().unwrap();
+                let end = time + duration(&self.keys[index]);
+                running.push(Reverse((end, worker, index)));
+                assignments.push(Assignment {
+                    key: self.keys[index].clone(),
+                    worker,
+                    start: time,
+                    end,
+                });
+            }
+
+            match running.peek() {
+                Some(&Reverse((end, _, _))) => time = end,
+                None => break,
+            }
+            while let Some(&Reverse((end, worker, index))) = running.peek() {
+                if end != time {
+                    break;
+                }
+                running.pop();
+                idle.push(Reverse(worker));
+                for &successor in self.successors[index].iter() {
+                    in_degrees[successor] -= 1;
+                    if in_degrees[successor] == 0 {
+                        ready.push(Reverse((priority(&self.keys[successor]), successor)));
+                    }
+                }
+            }
+        }
+
+        Ok(Schedule {
+            workers,
+            assignments,
+        })
+    }
+}
+
+impl<K> Default for Dag<K>
+where
+    K: Clone + Eq + Hash + Display,
+{
+    fn default() -> Self {
+        Dag::new()
+    }
+}
+
+/// A task done by a worker from `start` until just before `end`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Assignment<K> {
+    pub key: K,
+    pub worker: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// The result of scheduling a `Dag`, with assignments in the order they were started.
+///
+/// Displays as a timeline with a row for each point in time that a task starts or ends, and a
+/// column for each worker showing the task it is working on.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Schedule<K> {
+    pub workers: usize,
+    pub assignments: Vec<Assignment<K>>,
+}
+
+impl<K> Schedule<K> {
+    /// The time when all tasks are done.
+    pub fn makespan(&self) -> u64 {
+        self.assignments
+            .iter()
+            .map(|assignment| assignment.end)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The task a worker is working on at a point in time.
+    pub fn working_on(&self, worker: usize, time: u64) -> Option<&K> {
+        self.assignments
+            .iter()
+            .find(|a| a.worker == worker && a.start <= time && time < a.end)
+            .map(|a| &a.key)
+    }
+}
+
+impl<K> Display for Schedule<K>
+where
+    K: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        let headers = (1..=self.workers)
+            .map(|worker| format!("Worker {}", worker))
+            .collect::<Vec<_>>();
+        let width = self
+            .assignments
+            .iter()
+            .map(|a| a.key.to_string().chars().count())
+            .chain(headers.iter().map(|h| h.len()))
+            .max()
+            .unwrap_or(0);
+        let time_width = self.makespan().to_string().len().max("Time".len());
+
+        let mut header = format!("{:>w$}", "Time", w = time_width);
+        for worker in headers.iter() {
+            header += &format!("  {:^w$}", worker, w = width);
+        }
+        write!(f, "{}", header.trim_end())?;
+
+        let times = self
+            .assignments
+            .iter()
+            .flat_map(|a| vec![a.start, a.end])
+            .collect::<BTreeSet<_>>();
+        for time in times {
+            let mut row = format!("{:>w$}", time, w = time_width);
+            for worker in 0..self.workers {
+                let task = self
+                    .working_on(worker, time)
+                    .map_or_else(|| ".".to_string(), |key| key.to_string());
+                row += &format!("  {:^w$}", task, w = width);
+            }
+            write!(f, "\n{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Dag<char> {
+        let mut dag = Dag::new();
+        for &(before, after) in [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ]
+        .iter()
+        {
+            dag.add_edge(before, after);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_order() -> Result<(), Error> {
+        let dag = sample();
+        assert_eq!(dag.len(), 6);
+        assert_eq!(dag.order_by(|&c| c)?.iter().collect::<String>(), "CABDFE");
+        assert_eq!(
+            dag.order_by(|&c| Reverse(c))?.iter().collect::<String>(),
+            "CFADBE"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule() -> Result<(), Error> {
+        let dag = sample();
+        let schedule = dag.schedule(2, |&c| c, |&c| u64::from(c as u8 - b'A' + 1))?;
+        assert_eq!(schedule.makespan(), 15);
+        assert_eq!(schedule.working_on(0, 3), Some(&'A'));
+        assert_eq!(schedule.working_on(1, 3), Some(&'F'));
+        assert_eq!(schedule.working_on(1, 9), None);
+        assert_eq!(
+            schedule.to_string(),
+            "Time  Worker 1  Worker 2
+   0     C         .
+   3     A         F
+   4     B         F
+   6     D         F
+   9     D         .
+  10     E         .
+  15     .         ."
+        );
+        assert!(dag.schedule(0, |&c| c, |_| 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut dag = sample();
+        assert_eq!(dag.find_cycle(), None);
+        dag.add_edge('E', 'C');
+        dag.add_edge('G', 'A');
+        let cycle = dag.find_cycle().unwrap();
+        assert_eq!(cycle, vec!['C', 'A', 'B', 'E', 'C']);
+        let error = dag.order_by(|&c| c).unwrap_err();
+        assert_eq!(error.to_string(), "Dependency cycle C -> A -> B -> E -> C");
+
+        let mut dag = Dag::new();
+        dag.add_edge("loop".to_string(), "loop".to_string());
+        assert_eq!(dag.find_cycle(), Some(vec!["loop".to_string(); 2]));
+    }
+}
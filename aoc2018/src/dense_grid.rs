@@ -1,9 +1,9 @@
 #![allow(
-dead_code,
-unused_imports,
-clippy::needless_range_loop,
-clippy::ptr_arg,
-clippy::char_lit_as_u8
+    dead_code,
+    unused_imports,
+    clippy::needless_range_loop,
+    clippy::ptr_arg,
+    clippy::char_lit_as_u8
 )]
 
 use crate::position::*;
@@ -23,12 +23,7 @@ where
     T: Debug + Clone + Eq + Default,
 {
     pub values: Vec<T>,
-    pub min_x: Scalar,
-    pub min_y: Scalar,
-    pub max_x: Scalar,
-    pub max_y: Scalar,
-    pub height: usize,
-    pub width: usize,
+    pub bounds: Rect,
 }
 
 impl<T> Grid<T>
@@ -36,58 +31,43 @@ where
     T: Debug + Clone + Eq + Default,
 {
     pub fn new(min_x: Scalar, min_y: Scalar, max_x: Scalar, max_y: Scalar) -> Grid<T> {
-        let width = (max_x - min_x + 1) as usize;
-        let height = (max_y - min_y + 1) as usize;
-        let positions = width * height;
-        let values = vec![T::default(); positions];
-        Grid {
-            values,
-            min_x,
-            min_y,
-            max_x,
-            max_y,
-            height,
-            width,
-        }
+        Grid::from_bounds(Rect::new((min_x, min_y).into(), (max_x, max_y).into()))
     }
 
     pub fn from_origo(width: usize, height: usize) -> Grid<T> {
-        let positions = width * height;
-        let values = vec![T::default(); positions];
-        Grid {
-            values,
-            min_x: 0,
-            min_y: 0,
-            max_x: (width - 1) as i32,
-            max_y: (height - 1) as i32,
-            height,
-            width,
-        }
+        Grid::from_bounds(Rect::from_origo(width, height))
+    }
+
+    pub fn from_bounds(bounds: Rect) -> Grid<T> {
+        let values = vec![T::default(); bounds.area()];
+        Grid { values, bounds }
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.height()
     }
 
     pub fn in_bounds(&self, position: Position) -> bool {
-        self.min_x <= position.x
-            && position.x <= self.max_x
-            && self.min_y <= position.y
-            && position.y <= self.max_y
+        self.bounds.contains(position)
     }
 
-    fn index(&self, position: Position) -> usize {
-        if self.min_x == 0 && self.min_y == 0 {
-            position.y as usize * self.width + position.x as usize
+    fn index(&self, position: Position) -> Option<usize> {
+        if self.in_bounds(position) {
+            let offset = position - self.bounds.min;
+            Some(offset.y as usize * self.width() + offset.x as usize)
         } else {
-            panic!("Unhandled case right now");
+            None
         }
     }
 
     pub fn insert(&mut self, position: Position, value: T) {
-        if !self.in_bounds(position) {
-            panic!(format!(
-                "Position {} is not in bounds {}, {}, {}, {}",
-                position, self.min_x, self.max_x, self.min_y, self.max_y
-            ));
-        }
-        let index = self.index(position);
+        let index = self
+            .index(position)
+            .unwrap_or_else(|| panic!("Position {} is not in bounds {}", position, self.bounds));
         self.values[index] = value;
     }
 
@@ -95,16 +75,27 @@ where
     where
         I: Into<Position>,
     {
-        self.values.get(self.index(position.into()))
+        let index = self.index(position.into())?;
+        self.values.get(index)
     }
 
     pub fn get_mut<I>(&mut self, position: I) -> Option<&mut T>
     where
         I: Into<Position>,
     {
-        let index = self.index(position.into());
+        let index = self.index(position.into())?;
         self.values.get_mut(index)
     }
+
+    /// All positions in the grid, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        self.bounds.positions()
+    }
+
+    /// All positions and their values, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.values.iter())
+    }
 }
 
 impl<T> Index<Position> for Grid<T>
@@ -178,17 +169,42 @@ where
             .map(|v| format!("{}", v).len())
             .max()
             .unwrap_or(1);
-        let filler = " ".repeat(width);
-        for y in self.min_y..=self.max_y {
-            for x in self.min_x..=self.max_x {
-                if let Some(v) = self.get((x, y)) {
-                    write!(f, "{:width$}", v, width = width)?;
-                } else {
-                    write!(f, "{}", filler)?;
-                }
+        for (position, v) in self.iter() {
+            write!(f, "{:width$}", v, width = width)?;
+            if position.x == self.bounds.max.x {
+                writeln!(f)?;
             }
-            writeln!(f)?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_bounds() {
+        let mut grid: Grid<i32> = Grid::new(-2, 3, 1, 4);
+        assert_eq!((grid.width(), grid.height()), (4, 2));
+        grid[(-2, 3)] = 1;
+        grid[(1, 4)] = 2;
+        assert_eq!(grid.values.first(), Some(&1));
+        assert_eq!(grid.values.last(), Some(&2));
+        assert_eq!(grid.get((2, 3)), None);
+        assert_eq!(grid.to_string(), "1000\n0002\n");
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let mut grid: Grid<i32> = Grid::new(0, 0, 2, 1);
+        grid[(0, 1)] = 5;
+        // Past the end of a row is outside the grid, not the start of the next row.
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((-1, 1)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.get((0, -1)), None);
+        assert!(grid.get_mut((3, 0)).is_none());
+        assert_eq!(grid.get((0, 1)), Some(&5));
+    }
+}
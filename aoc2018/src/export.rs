@@ -0,0 +1,410 @@
+use crate::dense_grid;
+use crate::matrix::Matrix;
+use crate::position::*;
+use crate::sparse_grid;
+use failure::Error;
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use strum_macros::EnumString;
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour::new(0, 0, 0);
+    pub const WHITE: Colour = Colour::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Colour {
+        Colour { r, g, b }
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+#[derive(EnumString, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Format {
+    #[strum(serialize = "ppm")]
+    Ppm,
+    #[strum(serialize = "png")]
+    Png,
+    #[strum(serialize = "svg")]
+    Svg,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Svg => "svg",
+        }
+    }
+}
+
+/// An image with one colour per grid cell, drawn as `scale` by `scale` pixels.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub scale: usize,
+    cells: Vec<Colour>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Colour) -> Image {
+        Image {
+            width,
+            height,
+            scale: 1,
+            cells: vec![background; width * height],
+        }
+    }
+
+    /// Draw each position of `bounds` with the colour given for it.
+    pub fn from_fn<F>(bounds: Rect, mut colour: F) -> Image
+    where
+        F: FnMut(Position) -> Colour,
+    {
+        let mut image = Image::new(bounds.width(), bounds.height(), Colour::BLACK);
+        for position in bounds.positions() {
+            let offset = position - bounds.min;
+            image.set(offset.x as usize, offset.y as usize, colour(position));
+        }
+        image
+    }
+
+    pub fn from_dense_grid<T, F>(grid: &dense_grid::Grid<T>, mut colour: F) -> Image
+    where
+        T: Debug + Clone + Eq + Default,
+        F: FnMut(&T) -> Colour,
+    {
+        Image::from_fn(grid.bounds, |position| colour(&grid[position]))
+    }
+
+    /// Draw a sparse grid, using `background` for positions without values.
+    pub fn from_sparse_grid<T, F>(
+        grid: &sparse_grid::Grid<T>,
+        background: Colour,
+        mut colour: F,
+    ) -> Image
+    where
+        T: Debug + Clone + Eq,
+        F: FnMut(&T) -> Colour,
+    {
+        match grid.bounds {
+            Some(bounds) => Image::from_fn(bounds, |position| {
+                grid.get(position).map_or(background, &mut colour)
+            }),
+            None => Image::new(0, 0, background),
+        }
+    }
+
+    pub fn from_matrix(matrix: &Matrix, on: Colour, off: Colour) -> Image {
+        let mut image = Image::new(matrix.width, matrix.height, off);
+        for y in 0..matrix.height {
+            for x in 0..matrix.width {
+                if matrix[(x, y)] {
+                    image.set(x, y, on);
+                }
+            }
+        }
+        image
+    }
+
+    pub fn with_scale(mut self, scale: usize) -> Image {
+        assert!(scale > 0, "The scale must be positive");
+        self.scale = scale;
+        self
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Colour {
+        assert!(x < self.width && y < self.height);
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Colour) {
+        assert!(x < self.width && y < self.height);
+        self.cells[y * self.width + x] = colour;
+    }
+
+    pub fn pixel_width(&self) -> usize {
+        self.width * self.scale
+    }
+
+    pub fn pixel_height(&self) -> usize {
+        self.height * self.scale
+    }
+
+    /// The RGB bytes of one row of pixels, after scaling.
+    fn pixel_row(&self, pixel_y: usize) -> Vec<u8> {
+        let y = pixel_y / self.scale;
+        let mut row = Vec::with_capacity(3 * self.pixel_width());
+        for x in 0..self.width {
+            let colour = self.get(x, y);
+            for _ in 0..self.scale {
+                row.extend_from_slice(&[colour.r, colour.g, colour.b]);
+            }
+        }
+        row
+    }
+
+    pub fn write(&self, format: Format, out: &mut impl Write) -> Result<(), Error> {
+        match format {
+            Format::Ppm => self.write_ppm(out),
+            Format::Png => self.write_png(out),
+            Format::Svg => self.write_svg(out),
+        }
+    }
+
+    /// Write as a binary PPM (P6) image.
+    pub fn write_ppm(&self, out: &mut impl Write) -> Result<(), Error> {
+        write!(
+            out,
+            "P6\n{} {}\n255\n",
+            self.pixel_width(),
+            self.pixel_height()
+        )?;
+        for pixel_y in 0..self.pixel_height() {
+            out.write_all(&self.pixel_row(pixel_y))?;
+        }
+        Ok(())
+    }
+
+    /// Write as a PNG image, with the image data stored uncompressed.
+    pub fn write_png(&self, out: &mut impl Write) -> Result<(), Error> {
+        let mut raw = Vec::with_capacity((3 * self.pixel_width() + 1) * self.pixel_height());
+        for pixel_y in 0..self.pixel_height() {
+            raw.push(0);
+            raw.extend(self.pixel_row(pixel_y));
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.pixel_width() as u32).to_be_bytes());
+        header.extend_from_slice(&(self.pixel_height() as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_png_chunk(out, b"IHDR", &header)?;
+        write_png_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+        write_png_chunk(out, b"IEND", &[])?;
+        Ok(())
+    }
+
+    /// Write as an SVG image with one square per cell.
+    pub fn write_svg(&self, out: &mut impl Write) -> Result<(), Error> {
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" shape-rendering="crispEdges">"#,
+            self.pixel_width(),
+            self.pixel_height()
+        )?;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                    x * self.scale,
+                    y * self.scale,
+                    self.get(x, y).hex(),
+                    s = self.scale
+                )?;
+            }
+        }
+        writeln!(out, "</svg>")?;
+        Ok(())
+    }
+
+    pub fn save<P>(&self, path: P, format: Format) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(format, &mut out)?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+fn write_png_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> Result<(), Error> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data.iter()));
+    out.write_all(&crc.to_be_bytes())?;
+    Ok(())
+}
+
+fn crc32<'a, I>(bytes: I) -> u32
+where
+    I: IntoIterator<Item = &'a u8>,
+{
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Wrap data in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        result.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let length = block.len() as u16;
+        result.push(if last { 1 } else { 0 });
+        result.extend_from_slice(&length.to_le_bytes());
+        result.extend_from_slice(&(!length).to_le_bytes());
+        result.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    result.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    result
+}
+
+/// Writes images as numbered files, for turning a simulation into an animation.
+#[derive(Debug, Clone)]
+pub struct FrameWriter {
+    directory: PathBuf,
+    prefix: String,
+    format: Format,
+    next: usize,
+}
+
+impl FrameWriter {
+    /// Write frames into `directory`, creating it if needed.
+    pub fn new<P>(directory: P, prefix: &str, format: Format) -> Result<FrameWriter, Error>
+    where
+        P: AsRef<Path>,
+    {
+        fs::create_dir_all(directory.as_ref())?;
+        Ok(FrameWriter {
+            directory: directory.as_ref().to_owned(),
+            prefix: prefix.to_owned(),
+            format,
+            next: 0,
+        })
+    }
+
+    /// The number of frames written so far.
+    pub fn count(&self) -> usize {
+        self.next
+    }
+
+    /// Write the next frame, returning the path it was written to.
+    pub fn write(&mut self, image: &Image) -> Result<PathBuf, Error> {
+        let name = format!(
+            "{}{:05}.{}",
+            self.prefix,
+            self.next,
+            self.format.extension()
+        );
+        let path = self.directory.join(name);
+        image.save(&path, self.format)?;
+        self.next += 1;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkers() -> Image {
+        let bounds = Rect::new(Position::new(-1, 0), Position::new(1, 1));
+        Image::from_fn(bounds, |p| {
+            if (p.x + p.y) % 2 == 0 {
+                Colour::WHITE
+            } else {
+                Colour::new(255, 0, 0)
+            }
+        })
+    }
+
+    #[test]
+    fn test_ppm() -> Result<(), Error> {
+        let mut out = Vec::new();
+        checkers().with_scale(2).write_ppm(&mut out)?;
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&out[..header.len()], &header[..]);
+        assert_eq!(out.len(), header.len() + 6 * 4 * 3);
+        assert_eq!(
+            &out[header.len()..header.len() + 9],
+            &[255, 0, 0, 255, 0, 0, 255, 255, 255]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_png() -> Result<(), Error> {
+        let mut out = Vec::new();
+        checkers().write_png(&mut out)?;
+        assert_eq!(&out[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&out[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(&out[out.len() - 8..], b"IEND\xae\x42\x60\x82");
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg() -> Result<(), Error> {
+        let mut out = Vec::new();
+        checkers().with_scale(3).write_svg(&mut out)?;
+        let svg = String::from_utf8(out)?;
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="9" height="6""#));
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(svg.contains(r##"<rect x="3" y="0" width="3" height="3" fill="#ffffff"/>"##));
+        Ok(())
+    }
+
+    #[test]
+    fn test_grids() {
+        let mut sparse = sparse_grid::Grid::new();
+        sparse.insert(Position::new(2, 2), true);
+        sparse.insert(Position::new(3, 3), false);
+        let image = Image::from_sparse_grid(&sparse, Colour::BLACK, |&v| {
+            if v {
+                Colour::WHITE
+            } else {
+                Colour::new(0, 0, 255)
+            }
+        });
+        assert_eq!((image.width, image.height), (2, 2));
+        assert_eq!(image.get(0, 0), Colour::WHITE);
+        assert_eq!(image.get(1, 0), Colour::BLACK);
+        assert_eq!(image.get(1, 1), Colour::new(0, 0, 255));
+
+        let matrix: Matrix = "#./.#".parse().unwrap();
+        let image = Image::from_matrix(&matrix, Colour::WHITE, Colour::BLACK);
+        assert_eq!(image, {
+            let mut grid = dense_grid::Grid::from_origo(2, 2);
+            grid[(0, 0)] = true;
+            grid[(1, 1)] = true;
+            Image::from_dense_grid(&grid, |&v| if v { Colour::WHITE } else { Colour::BLACK })
+        });
+    }
+}
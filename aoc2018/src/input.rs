@@ -37,7 +37,7 @@ where
                     "Could not convert \"{}\" from \"{}\", reason is {:?}",
                     m.as_str(),
                     input,
-                    err.description()
+                    err.to_string()
                 );
                 Err(failure::err_msg(message))
             }
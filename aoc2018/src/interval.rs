@@ -0,0 +1,388 @@
+use crate::position::*;
+use num::PrimInt;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::ops::Range;
+
+/// A set of integers stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RangeSet<T>
+where
+    T: PrimInt,
+{
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> RangeSet<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> RangeSet<T> {
+        RangeSet { ranges: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges of the set in increasing order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    /// The number of integers in the set.
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |total, range| total + (range.end - range.start))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, Some(merged));
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first >= last {
+            return;
+        }
+        let mut kept = Vec::with_capacity(2);
+        if self.ranges[first].start < range.start {
+            kept.push(self.ranges[first].start..range.start);
+        }
+        if range.end < self.ranges[last - 1].end {
+            kept.push(range.end..self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+}
+
+impl<T> Default for RangeSet<T>
+where
+    T: PrimInt,
+{
+    fn default() -> Self {
+        RangeSet::new()
+    }
+}
+
+impl<T> FromIterator<Range<T>> for RangeSet<T>
+where
+    T: PrimInt,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut result = RangeSet::new();
+        for range in ranges {
+            result.insert(range);
+        }
+        result
+    }
+}
+
+/// Counts how many times each integer is covered by a collection of half-open ranges.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CoverageMap<T>
+where
+    T: PrimInt,
+{
+    /// The change in coverage at each range boundary.
+    deltas: BTreeMap<T, i64>,
+}
+
+impl<T> CoverageMap<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> CoverageMap<T> {
+        CoverageMap {
+            deltas: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    /// Add `count` to the coverage of all integers in `range`, where `count` may be negative.
+    pub fn add(&mut self, range: Range<T>, count: i64) {
+        if range.start >= range.end || count == 0 {
+            return;
+        }
+        for &(at, delta) in [(range.start, count), (range.end, -count)].iter() {
+            let entry = self.deltas.entry(at).or_insert(0);
+            *entry += delta;
+            if *entry == 0 {
+                self.deltas.remove(&at);
+            }
+        }
+    }
+
+    /// The coverage of a single integer.
+    pub fn depth(&self, value: T) -> i64 {
+        self.deltas.range(..=value).map(|(_, &delta)| delta).sum()
+    }
+
+    /// Maximal ranges of constant non-zero coverage in increasing order, with their coverage.
+    pub fn segments(&self) -> Vec<(Range<T>, i64)> {
+        let mut segments: Vec<(Range<T>, i64)> = Vec::new();
+        let mut depth = 0;
+        let mut start = None;
+        for (&at, &delta) in self.deltas.iter() {
+            if let Some(start) = start {
+                if depth != 0 {
+                    segments.push((start..at, depth));
+                }
+            }
+            depth += delta;
+            start = Some(at);
+        }
+        segments
+    }
+
+    /// The integers covered at least `depth` times.
+    pub fn covered(&self, depth: i64) -> RangeSet<T> {
+        self.segments()
+            .into_iter()
+            .filter(|&(_, covered)| covered >= depth)
+            .map(|(range, _)| range)
+            .collect()
+    }
+
+    /// The largest coverage and the first range that has it.
+    pub fn max_depth(&self) -> Option<(Range<T>, i64)> {
+        self.segments()
+            .into_iter()
+            .fold(
+                None,
+                |best: Option<(Range<T>, i64)>, (range, depth)| match best {
+                    Some((_, best_depth)) if best_depth >= depth => best,
+                    _ => Some((range, depth)),
+                },
+            )
+    }
+}
+
+impl<T> Default for CoverageMap<T>
+where
+    T: PrimInt,
+{
+    fn default() -> Self {
+        CoverageMap::new()
+    }
+}
+
+/// The half-open range of x coordinates of an inclusive rectangle.
+fn x_range(rect: &Rect) -> Range<i64> {
+    i64::from(rect.min.x)..i64::from(rect.max.x) + 1
+}
+
+/// The half-open range of y coordinates of an inclusive rectangle.
+fn y_range(rect: &Rect) -> Range<i64> {
+    i64::from(rect.min.y)..i64::from(rect.max.y) + 1
+}
+
+/// The number of positions covered by at least `depth` of the rectangles, using a sweep line.
+pub fn covered_area(rects: &[Rect], depth: i64) -> i64 {
+    let mut events = rects
+        .iter()
+        .flat_map(|rect| {
+            let x = x_range(rect);
+            vec![(x.start, 1, y_range(rect)), (x.end, -1, y_range(rect))]
+        })
+        .collect::<Vec<_>>();
+    events.sort_by_key(|&(x, _, _)| x);
+
+    let mut coverage = CoverageMap::new();
+    let mut area = 0;
+    let mut previous = None;
+    for (x, count, range) in events {
+        if let Some(previous) = previous {
+            if x > previous {
+                area += coverage.covered(depth).total_len() * (x - previous);
+            }
+        }
+        coverage.add(range, count);
+        previous = Some(x);
+    }
+    area
+}
+
+/// All pairs of indices of rectangles that overlap, with the smaller index first, in order.
+pub fn overlapping_pairs(rects: &[Rect]) -> Vec<(usize, usize)> {
+    let mut order = (0..rects.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&index| rects[index].min.x);
+
+    let mut pairs = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    for index in order {
+        let rect = &rects[index];
+        active.retain(|&other| rects[other].max.x >= rect.min.x);
+        for &other in active.iter() {
+            if rects[other].intersection(rect).is_some() {
+                pairs.push((other.min(index), other.max(index)));
+            }
+        }
+        active.push(index);
+    }
+    pairs.sort();
+    pairs
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_set() {
+        let mut set: RangeSet<i32> = vec![5..8, 1..3, 10..12].into_iter().collect();
+        assert_eq!(set.ranges(), &[1..3, 5..8, 10..12]);
+        assert_eq!(set.total_len(), 7);
+
+        set.insert(3..5);
+        assert_eq!(set.ranges(), &[1..8, 10..12]);
+        set.insert(9..9);
+        assert_eq!(set.ranges(), &[1..8, 10..12]);
+        set.insert(0..20);
+        assert_eq!(set.ranges(), &[0..20]);
+
+        set.remove(5..7);
+        set.remove(18..30);
+        assert_eq!(set.ranges(), &[0..5, 7..18]);
+        assert!(set.contains(0));
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert!(!set.contains(6));
+        assert!(set.contains(7));
+        assert!(!set.contains(18));
+        assert!(!set.contains(-1));
+
+        set.remove(-5..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: RangeSet<i64> = vec![0..10, 20..30].into_iter().collect();
+        let b: RangeSet<i64> = vec![5..25, 28..40].into_iter().collect();
+        assert_eq!(a.union(&b).ranges(), &[0..40]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 28..30]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..28]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 30..40]);
+        for value in -5..45 {
+            assert_eq!(
+                a.intersection(&b).contains(value),
+                a.contains(value) && b.contains(value)
+            );
+            assert_eq!(
+                a.union(&b).contains(value),
+                a.contains(value) || b.contains(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_coverage_map() {
+        let mut coverage = CoverageMap::new();
+        coverage.add(0..10, 1);
+        coverage.add(5..15, 1);
+        coverage.add(7..8, 2);
+        assert_eq!(
+            coverage.segments(),
+            vec![(0..5, 1), (5..7, 2), (7..8, 4), (8..10, 2), (10..15, 1)]
+        );
+        assert_eq!(coverage.depth(-1), 0);
+        assert_eq!(coverage.depth(7), 4);
+        assert_eq!(coverage.depth(15), 0);
+        assert_eq!(coverage.covered(2).ranges(), &[5..10]);
+        assert_eq!(coverage.max_depth(), Some((7..8, 4)));
+
+        coverage.add(7..8, -2);
+        coverage.add(5..15, -1);
+        assert_eq!(coverage.segments(), vec![(0..10, 1)]);
+        coverage.add(0..10, -1);
+        assert!(coverage.is_empty());
+    }
+
+    #[test]
+    fn test_rectangles() {
+        let rects = [
+            Rect::new((1, 3).into(), (4, 6).into()),
+            Rect::new((3, 1).into(), (6, 4).into()),
+            Rect::new((5, 5).into(), (6, 6).into()),
+        ];
+        assert_eq!(covered_area(&rects, 1), 32);
+        assert_eq!(covered_area(&rects, 2), 4);
+        assert_eq!(covered_area(&rects, 3), 0);
+        assert_eq!(overlapping_pairs(&rects), vec![(0, 1)]);
+
+        let touching = [
+            Rect::new((0, 0).into(), (2, 2).into()),
+            Rect::new((2, 2).into(), (3, 3).into()),
+            Rect::new((3, 0).into(), (3, 0).into()),
+        ];
+        assert_eq!(overlapping_pairs(&touching), vec![(0, 1)]);
+        assert_eq!(covered_area(&touching, 1), 13);
+        assert_eq!(covered_area(&touching, 2), 1);
+    }
+}
@@ -1,8 +1,16 @@
 pub mod union_find;
 pub use crate::union_find::UnionFind;
 
+pub mod dag;
 pub mod dense_grid;
+pub mod export;
 pub mod input;
+pub mod interval;
 pub mod matrix;
 pub mod position;
+pub mod ring;
+pub mod similarity;
 pub mod sparse_grid;
+pub mod summed_area;
+pub mod viz;
+pub mod voronoi;
@@ -1,49 +1,141 @@
-use failure::Error;
+use failure::{bail, Error};
+use hashbrown::HashMap;
+use lazy_static::lazy_static;
 
 use std::fmt;
-use std::ops::{Index, IndexMut};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Not};
 use std::str::FromStr;
 
+const WORD_BITS: usize = 64;
+
+/// A two-dimensional boolean matrix.
+///
+/// Cells are packed into `u64` words, row by row, with each row starting at a
+/// new word. Bits beyond the width in the last word of a row are always zero,
+/// so that counting, comparison and hashing can work on whole words.
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct Matrix {
     pub width: usize,
     pub height: usize,
-    data: Vec<bool>,
+    words_per_row: usize,
+    data: Vec<u64>,
 }
 
 impl Matrix {
     pub fn new(width: usize, height: usize) -> Matrix {
+        let words_per_row = width.div_ceil(WORD_BITS);
         Matrix {
             width,
             height,
-            data: vec![false; width * height],
+            words_per_row,
+            data: vec![0; words_per_row * height],
+        }
+    }
+
+    /// Build a matrix from values given in row-major order.
+    fn from_values<I>(width: usize, height: usize, values: I) -> Matrix
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        let mut result = Matrix::new(width, height);
+        for (index, value) in values.into_iter().enumerate().take(width * height) {
+            result.set(index % width, index / width, value);
         }
+        result
     }
 
     pub fn count_true(&self) -> usize {
-        self.data.iter().filter(|&&v| v).count()
+        self.data
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
     }
+
     pub fn count_false(&self) -> usize {
-        self.data.iter().filter(|&&v| !v).count()
+        self.width * self.height - self.count_true()
     }
 
-    fn pos(&self, x: usize, y: usize) -> usize {
+    fn pos(&self, x: usize, y: usize) -> (usize, u64) {
         assert!(x < self.width && y < self.height);
-        x + (y * self.width)
+        (y * self.words_per_row + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    /// The mask of valid bits in the last word of each row.
+    fn last_word_mask(&self) -> u64 {
+        match self.width % WORD_BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    fn row_words(&self, y: usize) -> &[u64] {
+        &self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    fn row_words_mut(&mut self, y: usize) -> &mut [u64] {
+        let words_per_row = self.words_per_row;
+        &mut self.data[y * words_per_row..(y + 1) * words_per_row]
+    }
+
+    /// Clear the padding bits after the last column in every row.
+    fn clear_padding(&mut self) {
+        if self.words_per_row > 0 {
+            let mask = self.last_word_mask();
+            let last = self.words_per_row - 1;
+            for row in self.data.chunks_mut(self.words_per_row) {
+                row[last] &= mask;
+            }
+        }
+    }
+
+    /// Apply `update(word, mask)` to the words covering columns `start_x..start_x + width` in row `y`.
+    fn update_row_range<F>(&mut self, y: usize, start_x: usize, width: usize, update: F)
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        if width == 0 {
+            return;
+        }
+        assert!(start_x + width <= self.width && y < self.height);
+        let end_x = start_x + width;
+        let row = self.row_words_mut(y);
+        let mut x = start_x;
+        while x < end_x {
+            let bit = x % WORD_BITS;
+            let bits = (WORD_BITS - bit).min(end_x - x);
+            let mask = if bits == WORD_BITS {
+                !0
+            } else {
+                ((1 << bits) - 1) << bit
+            };
+            let word = &mut row[x / WORD_BITS];
+            *word = update(*word, mask);
+            x += bits;
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (word, mask) = self.pos(x, y);
+        self.data[word] & mask != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        let (word, mask) = self.pos(x, y);
+        if value {
+            self.data[word] |= mask;
+        } else {
+            self.data[word] &= !mask;
+        }
     }
 
     pub fn slice(&self, start_x: usize, start_y: usize, width: usize, height: usize) -> Matrix {
         let mut data = Vec::with_capacity(width * height);
-        for x in start_x..(start_x + width) {
-            for y in start_y..(start_y + height) {
+        for y in start_y..(start_y + height) {
+            for x in start_x..(start_x + width) {
                 data.push(self[(x, y)])
             }
         }
-        Matrix {
-            width,
-            height,
-            data,
-        }
+        Matrix::from_values(width, height, data)
     }
 
     pub fn fill_with(
@@ -54,9 +146,11 @@ impl Matrix {
         height: usize,
         value: bool,
     ) {
-        for x_offset in 0..width {
-            for y_offset in 0..height {
-                self[(start_x + x_offset, start_y + y_offset)] = value;
+        for y in start_y..(start_y + height) {
+            if value {
+                self.update_row_range(y, start_x, width, |word, mask| word | mask);
+            } else {
+                self.update_row_range(y, start_x, width, |word, mask| word & !mask);
             }
         }
     }
@@ -72,81 +166,219 @@ impl Matrix {
     pub fn fill_from(&mut self, x: usize, y: usize, source: &Matrix) {
         for source_x in 0..source.width {
             for source_y in 0..source.height {
-                self[(x + source_x, y + source_y)] = source[(source_x, source_y)];
+                self.set(x + source_x, y + source_y, source[(source_x, source_y)]);
             }
         }
     }
 
     pub fn invert(&mut self, start_x: usize, start_y: usize, width: usize, height: usize) {
-        for x_offset in 0..width {
-            for y_offset in 0..height {
-                let previous_value = self[(start_x + x_offset, start_y + y_offset)];
-                self[(start_x + x_offset, start_y + y_offset)] = !previous_value;
-            }
+        for y in start_y..(start_y + height) {
+            self.update_row_range(y, start_x, width, |word, mask| word ^ mask);
         }
     }
 
     pub fn row(&self, y: usize) -> Matrix {
-        let mut result = Matrix::new(self.width, 1);
-        for x in 0..self.width {
-            result[(x, 1)] = self[(x, y)];
+        self.slice(0, y, self.width, 1)
+    }
+
+    pub fn col(&self, x: usize) -> Matrix {
+        self.slice(x, 0, 1, self.height)
+    }
+
+    /// Shift the words of a row by `dx` columns, filling vacated columns with false.
+    fn shift_row(&self, y: usize, dx: isize) -> Vec<u64> {
+        let source = self.row_words(y);
+        let words = source.len();
+        let mut result = vec![0; words];
+        let distance = dx.unsigned_abs();
+        let (word_shift, bit_shift) = (distance / WORD_BITS, distance % WORD_BITS);
+        for (index, target) in result.iter_mut().enumerate() {
+            let word_at = |offset: usize| -> u64 {
+                if dx >= 0 {
+                    index
+                        .checked_sub(word_shift + offset)
+                        .map_or(0, |source_index| source[source_index])
+                } else {
+                    source
+                        .get(index + word_shift + offset)
+                        .cloned()
+                        .unwrap_or(0)
+                }
+            };
+            *target = match (dx >= 0, bit_shift) {
+                (_, 0) => word_at(0),
+                (true, bits) => (word_at(0) << bits) | (word_at(1) >> (WORD_BITS - bits)),
+                (false, bits) => (word_at(0) >> bits) | (word_at(1) << (WORD_BITS - bits)),
+            };
+        }
+        if let Some(last) = result.last_mut() {
+            *last &= self.last_word_mask();
         }
         result
     }
 
-    pub fn col(&self, x: usize) -> Matrix {
-        let mut result = Matrix::new(1, self.height);
+    /// A copy with all cells moved by `(dx, dy)`, with vacated cells set to false.
+    pub fn shifted(&self, dx: isize, dy: isize) -> Matrix {
+        let mut result = Matrix::new(self.width, self.height);
         for y in 0..self.height {
-            result[(1, y)] = self[(x, y)];
+            let target_y = y as isize + dy;
+            if 0 <= target_y && (target_y as usize) < self.height {
+                let row = self.shift_row(y, dx);
+                result
+                    .row_words_mut(target_y as usize)
+                    .copy_from_slice(&row);
+            }
         }
         result
     }
 
+    /// Rotate row `y` so that the value at column `steps` moves to column 0.
     pub fn rotate_row(&mut self, y: usize, steps: usize) {
-        let row = self.row(y);
-        let rot_row: Vec<bool> = row.iter().cycle().skip(steps).take(self.width).collect();
-        for (offset, value) in rot_row.into_iter().enumerate() {
-            self[(offset, y)] = value;
+        if self.width == 0 {
+            return;
+        }
+        let steps = (steps % self.width) as isize;
+        let left = self.shift_row(y, -steps);
+        let right = self.shift_row(y, self.width as isize - steps);
+        for ((target, left), right) in self.row_words_mut(y).iter_mut().zip(left).zip(right) {
+            *target = left | right;
         }
     }
 
+    /// Rotate column `x` so that the value at row `steps` moves to row 0.
     pub fn rotate_col(&mut self, x: usize, steps: usize) {
-        let col = self.col(x);
-        let rot_col: Vec<bool> = col.iter().cycle().skip(steps).take(self.height).collect();
+        let col: Vec<bool> = (0..self.height).map(|y| self[(x, y)]).collect();
+        let rot_col: Vec<bool> = col
+            .iter()
+            .cycle()
+            .skip(steps)
+            .take(self.height)
+            .cloned()
+            .collect();
         for (offset, value) in rot_col.into_iter().enumerate() {
-            self[(x, offset)] = value;
+            self.set(x, offset, value);
         }
     }
 
-    pub fn rot90(&self) -> Matrix {
-        let mut result = Matrix::new(self.height, self.width);
-
-        for x in 0..self.width {
-            for y in 0..self.height {
-                let xr90 = y;
-                let yr90 = self.height - x - 1;
-                result[(xr90, yr90)] = self[(x, y)];
+    /// Build a matrix of the given size where each cell is taken from `source(x, y)`.
+    fn mapped<F>(&self, width: usize, height: usize, source: F) -> Matrix
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let mut result = Matrix::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let (source_x, source_y) = source(x, y);
+                result.set(x, y, self[(source_x, source_y)]);
             }
         }
-
         result
     }
 
+    /// Rotate 90 degrees counter-clockwise, so that the top right corner becomes the top left.
+    pub fn rot90(&self) -> Matrix {
+        let width = self.width;
+        self.mapped(self.height, self.width, |x, y| (width - y - 1, x))
+    }
+
+    pub fn rot180(&self) -> Matrix {
+        let (width, height) = (self.width, self.height);
+        self.mapped(width, height, |x, y| (width - x - 1, height - y - 1))
+    }
+
+    /// Rotate 90 degrees clockwise, so that the top left corner becomes the top right.
+    pub fn rot270(&self) -> Matrix {
+        let height = self.height;
+        self.mapped(self.height, self.width, |x, y| (y, height - x - 1))
+    }
+
+    /// Mirror left to right.
     pub fn flip(&self) -> Matrix {
-        let mut result = Matrix::new(self.width, self.height);
+        self.flip_horizontal()
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(&self) -> Matrix {
+        let width = self.width;
+        self.mapped(self.width, self.height, |x, y| (width - x - 1, y))
+    }
 
-        for x in 0..self.width {
-            for y in 0..self.height {
-                result[(self.width - x - 1, y)] = self[(x, y)];
+    /// Mirror top to bottom.
+    pub fn flip_vertical(&self) -> Matrix {
+        let height = self.height;
+        self.mapped(self.width, self.height, |x, y| (x, height - y - 1))
+    }
+
+    /// Mirror along the main diagonal.
+    pub fn transpose(&self) -> Matrix {
+        self.mapped(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// All eight rotations and reflections, starting with the matrix itself.
+    ///
+    /// Symmetric matrices give some orientations more than once.
+    pub fn orientations(&self) -> impl Iterator<Item = Matrix> {
+        let rotations = vec![self.clone(), self.rot90(), self.rot180(), self.rot270()];
+        let flipped = rotations
+            .iter()
+            .map(Matrix::flip_horizontal)
+            .collect::<Vec<_>>();
+        rotations.into_iter().chain(flipped)
+    }
+
+    /// The smallest of the eight orientations, equal for all matrices that are
+    /// rotations or reflections of each other.
+    pub fn canonical(&self) -> Matrix {
+        self.orientations()
+            .min()
+            .expect("There are always eight orientations")
+    }
+
+    fn is_blank_row(&self, y: usize) -> bool {
+        self.row_words(y).iter().all(|&word| word == 0)
+    }
+
+    fn is_blank_col(&self, x: usize) -> bool {
+        (0..self.height).all(|y| !self[(x, y)])
+    }
+
+    /// The smallest slice containing all true cells, or an empty matrix if there are none.
+    pub fn trim(&self) -> Matrix {
+        let mut rows = (0..self.height).filter(|&y| !self.is_blank_row(y));
+        let mut cols = (0..self.width).filter(|&x| !self.is_blank_col(x));
+        match (rows.next(), rows.next_back(), cols.next(), cols.next_back()) {
+            (Some(top), bottom, Some(left), right) => {
+                let (bottom, right) = (bottom.unwrap_or(top), right.unwrap_or(left));
+                self.slice(left, top, right - left + 1, bottom - top + 1)
             }
+            _ => Matrix::new(0, 0),
         }
-
-        result
     }
 
     pub fn iter(&self) -> MatrixIterator<'_> {
         self.into_iter()
     }
+
+    fn assert_same_size(&self, other: &Matrix) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Matrix sizes differ, {}x{} and {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+
+    fn combine<F>(&mut self, other: &Matrix, op: F)
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        self.assert_same_size(other);
+        for (word, &other_word) in self.data.iter_mut().zip(other.data.iter()) {
+            *word = op(*word, other_word);
+        }
+    }
 }
 
 impl<'a> IntoIterator for &'a Matrix {
@@ -161,6 +393,7 @@ impl<'a> IntoIterator for &'a Matrix {
     }
 }
 
+/// Iterator over the values of a matrix in row-major order.
 #[derive(Clone)]
 pub struct MatrixIterator<'a> {
     matrix: &'a Matrix,
@@ -170,10 +403,13 @@ pub struct MatrixIterator<'a> {
 impl<'a> Iterator for MatrixIterator<'a> {
     type Item = bool;
     fn next(&mut self) -> Option<bool> {
-        if self.index < self.matrix.data.len() {
-            let result = self.matrix.data[self.index];
+        if self.index < self.matrix.width * self.matrix.height {
+            let (x, y) = (
+                self.index % self.matrix.width,
+                self.index / self.matrix.width,
+            );
             self.index += 1;
-            Some(result)
+            Some(self.matrix.get(x, y))
         } else {
             None
         }
@@ -185,15 +421,77 @@ impl Index<(usize, usize)> for Matrix {
 
     fn index(&self, index: (usize, usize)) -> &Self::Output {
         let (x, y) = index;
-        &self.data[self.pos(x, y)]
+        if self.get(x, y) {
+            &true
+        } else {
+            &false
+        }
     }
 }
 
-impl IndexMut<(usize, usize)> for Matrix {
-    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        let (x, y) = index;
-        let position = self.pos(x, y);
-        &mut self.data[position]
+impl BitAndAssign<&Matrix> for Matrix {
+    fn bitand_assign(&mut self, rhs: &Matrix) {
+        self.combine(rhs, |a, b| a & b);
+    }
+}
+
+impl BitOrAssign<&Matrix> for Matrix {
+    fn bitor_assign(&mut self, rhs: &Matrix) {
+        self.combine(rhs, |a, b| a | b);
+    }
+}
+
+impl BitXorAssign<&Matrix> for Matrix {
+    fn bitxor_assign(&mut self, rhs: &Matrix) {
+        self.combine(rhs, |a, b| a ^ b);
+    }
+}
+
+impl BitAnd for &Matrix {
+    type Output = Matrix;
+
+    fn bitand(self, rhs: &Matrix) -> Matrix {
+        let mut result = self.clone();
+        result &= rhs;
+        result
+    }
+}
+
+impl BitOr for &Matrix {
+    type Output = Matrix;
+
+    fn bitor(self, rhs: &Matrix) -> Matrix {
+        let mut result = self.clone();
+        result |= rhs;
+        result
+    }
+}
+
+impl BitXor for &Matrix {
+    type Output = Matrix;
+
+    fn bitxor(self, rhs: &Matrix) -> Matrix {
+        let mut result = self.clone();
+        result ^= rhs;
+        result
+    }
+}
+
+impl Not for Matrix {
+    type Output = Matrix;
+
+    fn not(mut self) -> Matrix {
+        self.data.iter_mut().for_each(|word| *word = !*word);
+        self.clear_padding();
+        self
+    }
+}
+
+impl Not for &Matrix {
+    type Output = Matrix;
+
+    fn not(self) -> Matrix {
+        !self.clone()
     }
 }
 
@@ -211,7 +509,7 @@ impl FromStr for Matrix {
         let mut result = Matrix::new(width, height);
         for (y, row) in rows.iter().enumerate() {
             for (x, ch) in row.chars().enumerate() {
-                result[(x, y)] = ch == '#';
+                result.set(x, y, ch == '#');
             }
         }
         Ok(result)
@@ -229,3 +527,271 @@ impl fmt::Display for Matrix {
         Ok(())
     }
 }
+
+/// Glyphs of the 4 wide and 6 high font, each trimmed to its own columns.
+#[rustfmt::skip]
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##./#..#/#..#/####/#..#/#..#"),
+    ('B', "###./#..#/###./#..#/#..#/###."),
+    ('C', ".##./#..#/#.../#.../#..#/.##."),
+    ('E', "####/#.../###./#.../#.../####"),
+    ('F', "####/#.../###./#.../#.../#..."),
+    ('G', ".##./#..#/#.../#.##/#..#/.###"),
+    ('H', "#..#/#..#/####/#..#/#..#/#..#"),
+    ('I', "###/.#./.#./.#./.#./###"),
+    ('J', "..##/...#/...#/...#/#..#/.##."),
+    ('K', "#..#/#.#./##../#.#./#.#./#..#"),
+    ('L', "#.../#.../#.../#.../#.../####"),
+    ('O', ".##./#..#/#..#/#..#/#..#/.##."),
+    ('P', "###./#..#/#..#/###./#.../#..."),
+    ('R', "###./#..#/#..#/###./#.#./#..#"),
+    ('S', ".###/#.../#.../.##./...#/###."),
+    ('U', "#..#/#..#/#..#/#..#/#..#/.##."),
+    ('Y', "#...#/#...#/.#.#./..#../..#../..#.."),
+    ('Z', "####/...#/..#./.#../#.../####"),
+];
+
+/// Glyphs of the 6 wide and 10 high font, each trimmed to its own columns.
+#[rustfmt::skip]
+const LARGE_FONT: [(char, &str); 15] = [
+    ('A', "..##../.#..#./#....#/#....#/#....#/######/#....#/#....#/#....#/#....#"),
+    ('B', "#####./#....#/#....#/#....#/#####./#....#/#....#/#....#/#....#/#####."),
+    ('C', ".####./#....#/#...../#...../#...../#...../#...../#...../#....#/.####."),
+    ('E', "######/#...../#...../#...../#####./#...../#...../#...../#...../######"),
+    ('F', "######/#...../#...../#...../#####./#...../#...../#...../#...../#....."),
+    ('G', ".####./#....#/#...../#...../#...../#..###/#....#/#....#/#...##/.###.#"),
+    ('H', "#....#/#....#/#....#/#....#/######/#....#/#....#/#....#/#....#/#....#"),
+    ('J', "...###/....#./....#./....#./....#./....#./....#./#...#./#...#./.###.."),
+    ('K', "#....#/#...#./#..#../#.#.../##..../##..../#.#.../#..#../#...#./#....#"),
+    ('L', "#...../#...../#...../#...../#...../#...../#...../#...../#...../######"),
+    ('N', "#....#/##...#/##...#/#.#..#/#.#..#/#..#.#/#..#.#/#...##/#...##/#....#"),
+    ('P', "#####./#....#/#....#/#....#/#####./#...../#...../#...../#...../#....."),
+    ('R', "#####./#....#/#....#/#....#/#####./#..#../#...#./#...#./#....#/#....#"),
+    ('X', "#....#/#....#/.#..#./.#..#./..##../..##../.#..#./.#..#./#....#/#....#"),
+    ('Z', "######/.....#/.....#/....#./...#../..#.../.#..../#...../#...../######"),
+];
+
+lazy_static! {
+    /// Known glyphs by font height.
+    static ref FONTS: HashMap<usize, HashMap<Matrix, char>> = {
+        let mut fonts = HashMap::new();
+        for font in [&SMALL_FONT[..], &LARGE_FONT[..]].iter() {
+            for &(ch, glyph) in font.iter() {
+                let glyph = glyph.parse::<Matrix>().expect("Font glyphs are valid");
+                fonts
+                    .entry(glyph.height)
+                    .or_insert_with(HashMap::new)
+                    .insert(glyph.trim(), ch);
+            }
+        }
+        fonts
+    };
+}
+
+/// Read the text rendered in a matrix using the pixel fonts of Advent of Code.
+///
+/// Characters are separated by blank columns. Both the 4 by 6 and the 6 by 10
+/// fonts are known, chosen by the height of the text. Unknown glyphs give an
+/// error that shows the glyph.
+pub fn ocr(matrix: &Matrix) -> Result<String, Error> {
+    let text = matrix.trim();
+    if text.width == 0 {
+        return Ok(String::new());
+    }
+    let font = match FONTS.get(&text.height) {
+        Some(font) => font,
+        None => bail!("No known font is {} high, text is\n{}", text.height, text),
+    };
+
+    let mut result = String::new();
+    let mut x = 0;
+    while x < text.width {
+        if text.is_blank_col(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < text.width && !text.is_blank_col(x) {
+            x += 1;
+        }
+        let glyph = text.slice(start, 0, x - start, text.height);
+        match font.get(&glyph) {
+            Some(&ch) => result.push(ch),
+            None => bail!("Unrecognized glyph at column {}\n{}", start, glyph),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A matrix wider than one word, with a recognizable pattern.
+    fn wide() -> Matrix {
+        let mut matrix = Matrix::new(130, 3);
+        for x in (0..130).step_by(3) {
+            matrix.set(x, x % 3, true);
+            matrix.set(x, 1, true);
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_get_set_count() {
+        let mut matrix = Matrix::new(70, 2);
+        matrix.set(0, 0, true);
+        matrix.set(69, 1, true);
+        matrix.set(64, 0, true);
+        matrix.set(64, 0, false);
+        assert!(matrix[(0, 0)] && matrix[(69, 1)] && !matrix[(64, 0)]);
+        assert_eq!(matrix.count_true(), 2);
+        assert_eq!(matrix.count_false(), 138);
+        assert_eq!(matrix.iter().filter(|&v| v).count(), 2);
+    }
+
+    #[test]
+    fn test_fill_and_invert() {
+        let mut matrix = Matrix::new(200, 4);
+        matrix.fill_true(10, 1, 150, 2);
+        assert_eq!(matrix.count_true(), 300);
+        matrix.invert(0, 0, 200, 4);
+        assert_eq!(matrix.count_true(), 500);
+        matrix.fill_false(0, 0, 200, 4);
+        assert_eq!(matrix.count_true(), 0);
+        assert_eq!(!&matrix, {
+            let mut full = Matrix::new(200, 4);
+            full.fill_true(0, 0, 200, 4);
+            full
+        });
+        assert_eq!((!matrix).count_true(), 800);
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let a: Matrix = "##../#.#.".parse().unwrap();
+        let b: Matrix = "#.#./.##.".parse().unwrap();
+        assert_eq!(&a & &b, "#.../..#.".parse().unwrap());
+        assert_eq!(&a | &b, "###./###.".parse().unwrap());
+        assert_eq!(&a ^ &b, ".##./##..".parse().unwrap());
+        assert_eq!(!&a, "..##/.#.#".parse().unwrap());
+    }
+
+    #[test]
+    fn test_shifted() {
+        let matrix = wide();
+        for &(dx, dy) in &[(1, 0), (-1, 0), (64, 1), (-65, -1), (3, 2), (0, 0)] {
+            let shifted = matrix.shifted(dx, dy);
+            for y in 0..matrix.height {
+                for x in 0..matrix.width {
+                    let (source_x, source_y) = (x as isize - dx, y as isize - dy);
+                    let expected = 0 <= source_x
+                        && source_x < matrix.width as isize
+                        && 0 <= source_y
+                        && source_y < matrix.height as isize
+                        && matrix[(source_x as usize, source_y as usize)];
+                    assert_eq!(shifted[(x, y)], expected, "at ({}, {})", x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_row_col_slice() {
+        let matrix: Matrix = "#..#/.##./...#".parse().unwrap();
+        assert_eq!(matrix.row(0), "#..#".parse().unwrap());
+        assert_eq!(matrix.row(2), "...#".parse().unwrap());
+        assert_eq!(matrix.col(3), "#/./#".parse().unwrap());
+        assert_eq!(matrix.col(1), "./#/.".parse().unwrap());
+        assert_eq!(matrix.slice(1, 0, 3, 2), "..#/##.".parse().unwrap());
+        assert_eq!(matrix.slice(2, 1, 2, 2), "#./.#".parse().unwrap());
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let matrix: Matrix = "##./..#".parse().unwrap();
+        assert_eq!(matrix.rot90(), ".#/#./#.".parse().unwrap());
+        assert_eq!(matrix.rot180(), "#../.##".parse().unwrap());
+        assert_eq!(matrix.rot270(), ".#/.#/#.".parse().unwrap());
+        assert_eq!(matrix.flip(), ".##/#..".parse().unwrap());
+        assert_eq!(matrix.flip_vertical(), "..#/##.".parse().unwrap());
+        assert_eq!(matrix.transpose(), "#./#./.#".parse().unwrap());
+        assert_eq!(matrix.rot90().rot90().rot90().rot90(), matrix);
+        assert_eq!(matrix.rot90().rot270(), matrix);
+        assert_eq!(matrix.transpose(), matrix.rot90().flip_vertical());
+
+        let orientations = matrix.orientations().collect::<Vec<_>>();
+        assert_eq!(orientations.len(), 8);
+        assert_eq!(orientations[0], matrix);
+        assert!(orientations.contains(&matrix.transpose()));
+        assert!(orientations.contains(&matrix.flip_vertical()));
+        assert!(orientations
+            .iter()
+            .all(|m| m.canonical() == matrix.canonical()));
+        let other: Matrix = "#.#/.#.".parse().unwrap();
+        assert_ne!(other.canonical(), matrix.canonical());
+    }
+
+    #[test]
+    fn test_trim() {
+        let matrix: Matrix = "..../.#../..#./....".parse().unwrap();
+        assert_eq!(matrix.trim(), "#./.#".parse().unwrap());
+        assert_eq!(Matrix::new(3, 3).trim(), Matrix::new(0, 0));
+    }
+
+    /// Render glyphs with a blank column between them and blank margins around.
+    fn render(font: &[(char, &str)], text: &str) -> Matrix {
+        let glyphs = text
+            .chars()
+            .map(|ch| {
+                let &(_, glyph) = font.iter().find(|&&(c, _)| c == ch).unwrap();
+                glyph.parse::<Matrix>().unwrap()
+            })
+            .collect::<Vec<_>>();
+        let height = glyphs[0].height;
+        let width = glyphs.iter().map(|g| g.width + 1).sum::<usize>() + 3;
+        let mut result = Matrix::new(width, height + 2);
+        let mut x = 2;
+        for glyph in glyphs {
+            result.fill_from(x, 1, &glyph);
+            x += glyph.width + 1;
+        }
+        result
+    }
+
+    #[test]
+    fn test_ocr() {
+        let all_small = SMALL_FONT.iter().map(|&(ch, _)| ch).collect::<String>();
+        assert_eq!(ocr(&render(&SMALL_FONT, &all_small)).unwrap(), all_small);
+        let all_large = LARGE_FONT.iter().map(|&(ch, _)| ch).collect::<String>();
+        assert_eq!(ocr(&render(&LARGE_FONT, &all_large)).unwrap(), all_large);
+        assert_eq!(ocr(&Matrix::new(5, 5)).unwrap(), "");
+    }
+
+    #[test]
+    fn test_ocr_unknown() {
+        let mut matrix = render(&SMALL_FONT, "HI");
+        matrix.set(3, 1, true);
+        let error = ocr(&matrix).unwrap_err().to_string();
+        assert!(error.starts_with("Unrecognized glyph at column 0"));
+        assert!(
+            error.ends_with("##.#\n#..#\n####\n#..#\n#..#\n#..#\n"),
+            "{}",
+            error
+        );
+
+        assert!(ocr(&"#/#/#".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_rotate_row() {
+        for &steps in &[0, 1, 63, 64, 100, 129, 131] {
+            let mut matrix = wide();
+            matrix.rotate_row(1, steps);
+            for x in 0..130 {
+                assert_eq!(matrix[(x, 1)], wide()[((x + steps) % 130, 1)]);
+            }
+            assert_eq!(matrix.count_true(), wide().count_true());
+        }
+    }
+}
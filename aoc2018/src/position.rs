@@ -1,6 +1,10 @@
+use failure::{bail, Error};
+use std::cmp::{max, min, Ordering};
+use std::convert::TryFrom;
 use std::fmt::Display;
 use std::fmt::Formatter;
-use std::ops::Add;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 use strum_macros::EnumString;
 
 pub trait Step<T: Copy>
@@ -22,6 +26,74 @@ impl Position {
     pub fn new(x: Scalar, y: Scalar) -> Position {
         Position { x, y }
     }
+
+    /// The component-wise sign, useful for stepping one unit towards another position.
+    pub fn signum(self) -> Position {
+        Position::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The component-wise absolute value.
+    pub fn abs(self) -> Position {
+        Position::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Rotate 90 degrees around the origin.
+    ///
+    /// Since y grows downwards, turning right is clockwise on screen, so that
+    /// `North` rotated `Right` becomes `East`.
+    pub fn rotate(self, turn: Turn) -> Position {
+        match turn {
+            Turn::Left => Position::new(self.y, -self.x),
+            Turn::Right => Position::new(-self.y, self.x),
+            Turn::Straight => self,
+            Turn::Back => -self,
+        }
+    }
+
+    /// Rotate 90 degrees around `pivot`.
+    pub fn rotate_around(self, pivot: Position, turn: Turn) -> Position {
+        pivot + (self - pivot).rotate(turn)
+    }
+
+    /// Compare in reading order, that is top-to-bottom and then left-to-right.
+    ///
+    /// Note that the derived `Ord` compares `x` before `y`, which is column-major.
+    pub fn cmp_reading_order(&self, other: &Position) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+/// A position ordered in reading order, top-to-bottom and then left-to-right.
+///
+/// Use as a sort key when a simulation must process units in reading order,
+/// since the derived `Ord` on `Position` compares `x` first.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ReadingOrder(pub Position);
+
+impl Ord for ReadingOrder {
+    fn cmp(&self, other: &ReadingOrder) -> Ordering {
+        self.0.cmp_reading_order(&other.0)
+    }
+}
+
+impl PartialOrd for ReadingOrder {
+    fn partial_cmp(&self, other: &ReadingOrder) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<Position> for ReadingOrder {
+    fn from(position: Position) -> Self {
+        ReadingOrder(position)
+    }
+}
+
+/// Sort items in reading order of the position extracted by `position`.
+pub fn sort_reading_order<T, F>(items: &mut [T], mut position: F)
+where
+    F: FnMut(&T) -> Position,
+{
+    items.sort_by_key(|item| ReadingOrder(position(item)));
 }
 
 impl Display for Position {
@@ -86,42 +158,376 @@ impl From<&(usize, usize)> for Position {
     }
 }
 
-impl Add for Position {
+/// Implement the shared arithmetic for a point struct with the given coordinate fields.
+macro_rules! point_arithmetic {
+    ($name:ident { $($field:ident),+ }) => {
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, rhs: $name) -> $name {
+                $name { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Add for &$name {
+            type Output = $name;
+
+            fn add(self, rhs: &$name) -> $name {
+                *self + *rhs
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: $name) -> $name {
+                $name { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Sub for &$name {
+            type Output = $name;
+
+            fn sub(self, rhs: &$name) -> $name {
+                *self - *rhs
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<Scalar> for $name {
+            type Output = $name;
+
+            fn mul(self, rhs: Scalar) -> $name {
+                $name { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: $name) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: $name) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+    };
+}
+
+point_arithmetic!(Position { x, y });
+
+impl Add<Position> for &Position {
     type Output = Position;
 
     fn add(self, rhs: Position) -> Position {
-        (self.x + rhs.x, self.y + rhs.y).into()
+        *self + rhs
     }
 }
 
-impl Add for &Position {
+impl Add<&Position> for Position {
     type Output = Position;
 
     fn add(self, rhs: &Position) -> Position {
-        (self.x + rhs.x, self.y + rhs.y).into()
+        self + *rhs
     }
 }
 
-impl Add<Position> for &Position {
+impl Sub<Position> for &Position {
     type Output = Position;
 
-    fn add(self, rhs: Position) -> Position {
-        (self.x + rhs.x, self.y + rhs.y).into()
+    fn sub(self, rhs: Position) -> Position {
+        *self - rhs
     }
 }
 
-impl Add<&Position> for Position {
+impl Sub<&Position> for Position {
     type Output = Position;
 
-    fn add(self, rhs: &Position) -> Position {
-        (self.x + rhs.x, self.y + rhs.y).into()
+    fn sub(self, rhs: &Position) -> Position {
+        self - *rhs
+    }
+}
+
+/// An axis-aligned rectangle of positions, with both corners inclusive.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Rect {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Rect {
+    /// The rectangle spanned by two opposite corners, in any order.
+    pub fn new(a: Position, b: Position) -> Rect {
+        Rect {
+            min: Position::new(min(a.x, b.x), min(a.y, b.y)),
+            max: Position::new(max(a.x, b.x), max(a.y, b.y)),
+        }
+    }
+
+    /// The rectangle with top left corner at the origin and the given size.
+    pub fn from_origo(width: usize, height: usize) -> Rect {
+        assert!(width > 0 && height > 0, "A rectangle can not be empty");
+        Rect {
+            min: Position::new(0, 0),
+            max: Position::new(width as Scalar - 1, height as Scalar - 1),
+        }
+    }
+
+    /// The smallest rectangle containing all the points, if there are any.
+    pub fn from_points<I, P>(points: I) -> Option<Rect>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Position>,
+    {
+        bounding_box(points.into_iter().map(Into::into)).map(|(min, max)| Rect { min, max })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.within(&self.min, &self.max)
+    }
+
+    /// The positions in both rectangles, if any.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let low = Position::new(max(self.min.x, other.min.x), max(self.min.y, other.min.y));
+        let high = Position::new(min(self.max.x, other.max.x), min(self.max.y, other.max.y));
+        if low.x <= high.x && low.y <= high.y {
+            Some(Rect::new(low, high))
+        } else {
+            None
+        }
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: Position::new(min(self.min.x, other.min.x), min(self.min.y, other.min.y)),
+            max: Position::new(max(self.max.x, other.max.x), max(self.max.y, other.max.y)),
+        }
+    }
+
+    /// The smallest rectangle containing this rectangle and the position.
+    pub fn include(&self, position: Position) -> Rect {
+        self.union(&Rect::new(position, position))
+    }
+
+    /// Grow the rectangle by `margin` in all directions.
+    pub fn expand(&self, margin: Scalar) -> Rect {
+        Rect::new(
+            self.min - Position::new(margin, margin),
+            self.max + Position::new(margin, margin),
+        )
+    }
+
+    /// All positions in the rectangle, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let (min_x, max_x) = (self.min.x, self.max.x);
+        (self.min.y..=self.max.y).flat_map(move |y| (min_x..=max_x).map(move |x| Position { x, y }))
+    }
+}
+
+impl Display for Rect {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}-{}", self.min, self.max)
+    }
+}
+
+/// A point with a fixed number of integer coordinates.
+///
+/// Distances, neighbours and bounding boxes are defined once in terms of the
+/// coordinates, so that `Position`, `Position3` and `Position4` share them.
+pub trait Point
+where
+    Self: Copy + Clone + Eq + Hash,
+{
+    const DIMENSIONS: usize;
+
+    /// The coordinate along `axis`, which must be less than `DIMENSIONS`.
+    fn coordinate(&self, axis: usize) -> Scalar;
+
+    /// Build a point by computing each coordinate from its axis.
+    fn from_fn<F>(f: F) -> Self
+    where
+        F: FnMut(usize) -> Scalar;
+
+    fn origin() -> Self {
+        Self::from_fn(|_| 0)
+    }
+
+    /// The sum of the absolute coordinate differences.
+    fn manhattan(&self, other: &Self) -> Scalar {
+        (0..Self::DIMENSIONS)
+            .map(|axis| (self.coordinate(axis) - other.coordinate(axis)).abs())
+            .sum()
+    }
+
+    /// The largest absolute coordinate difference.
+    fn chebyshev(&self, other: &Self) -> Scalar {
+        (0..Self::DIMENSIONS)
+            .map(|axis| (self.coordinate(axis) - other.coordinate(axis)).abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Check if the point is inside the box spanned by the inclusive corners `low` and `high`.
+    fn within(&self, low: &Self, high: &Self) -> bool {
+        (0..Self::DIMENSIONS).all(|axis| {
+            low.coordinate(axis) <= self.coordinate(axis)
+                && self.coordinate(axis) <= high.coordinate(axis)
+        })
+    }
+}
+
+impl Point for Position {
+    const DIMENSIONS: usize = 2;
+
+    fn coordinate(&self, axis: usize) -> Scalar {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("Axis {} is out of range for a 2-D position", axis),
+        }
+    }
+
+    fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> Scalar,
+    {
+        let x = f(0);
+        let y = f(1);
+        Position { x, y }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Position3 {
+    pub x: Scalar,
+    pub y: Scalar,
+    pub z: Scalar,
+}
+
+impl Position3 {
+    pub fn new(x: Scalar, y: Scalar, z: Scalar) -> Position3 {
+        Position3 { x, y, z }
+    }
+}
+
+impl Display for Position3 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+impl From<(Scalar, Scalar, Scalar)> for Position3 {
+    fn from(pos: (Scalar, Scalar, Scalar)) -> Self {
+        Position3::new(pos.0, pos.1, pos.2)
+    }
+}
+
+impl Point for Position3 {
+    const DIMENSIONS: usize = 3;
+
+    fn coordinate(&self, axis: usize) -> Scalar {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Axis {} is out of range for a 3-D position", axis),
+        }
+    }
+
+    fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> Scalar,
+    {
+        let x = f(0);
+        let y = f(1);
+        let z = f(2);
+        Position3 { x, y, z }
+    }
+}
+
+point_arithmetic!(Position3 { x, y, z });
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Position4 {
+    pub x: Scalar,
+    pub y: Scalar,
+    pub z: Scalar,
+    pub w: Scalar,
+}
+
+impl Position4 {
+    pub fn new(x: Scalar, y: Scalar, z: Scalar, w: Scalar) -> Position4 {
+        Position4 { x, y, z, w }
+    }
+}
+
+impl Display for Position4 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "({},{},{},{})", self.x, self.y, self.z, self.w)
     }
 }
 
+impl From<(Scalar, Scalar, Scalar, Scalar)> for Position4 {
+    fn from(pos: (Scalar, Scalar, Scalar, Scalar)) -> Self {
+        Position4::new(pos.0, pos.1, pos.2, pos.3)
+    }
+}
+
+impl Point for Position4 {
+    const DIMENSIONS: usize = 4;
+
+    fn coordinate(&self, axis: usize) -> Scalar {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            3 => self.w,
+            _ => panic!("Axis {} is out of range for a 4-D position", axis),
+        }
+    }
+
+    fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> Scalar,
+    {
+        let x = f(0);
+        let y = f(1);
+        let z = f(2);
+        let w = f(3);
+        Position4 { x, y, z, w }
+    }
+}
+
+point_arithmetic!(Position4 { x, y, z, w });
+
 #[derive(EnumString, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum Turn {
     Left,
     Right,
+    Straight,
+    Back,
 }
 
 #[derive(EnumString, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
@@ -145,6 +551,18 @@ impl Cardinal {
             (East, Right) => South,
             (West, Left) => South,
             (West, Right) => North,
+            (_, Straight) => self,
+            (_, Back) => self.opposite(),
+        }
+    }
+
+    pub fn opposite(self) -> Cardinal {
+        use crate::position::Cardinal::*;
+        match self {
+            North => South,
+            South => North,
+            East => West,
+            West => East,
         }
     }
 }
@@ -169,6 +587,18 @@ impl Direction {
             (Right, Turn::Right) => Down,
             (Left, Turn::Left) => Down,
             (Left, Turn::Right) => Up,
+            (_, Turn::Straight) => self,
+            (_, Turn::Back) => self.opposite(),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        use crate::position::Direction::*;
+        match self {
+            Up => Down,
+            Down => Up,
+            Right => Left,
+            Left => Right,
         }
     }
 }
@@ -199,6 +629,135 @@ impl From<Direction> for Cardinal {
     }
 }
 
+/// The eight compass directions, including the diagonals.
+#[derive(EnumString, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Compass8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Compass8 {
+    /// All directions, clockwise starting from `North`.
+    pub const ALL: [Compass8; 8] = [
+        Compass8::North,
+        Compass8::NorthEast,
+        Compass8::East,
+        Compass8::SouthEast,
+        Compass8::South,
+        Compass8::SouthWest,
+        Compass8::West,
+        Compass8::NorthWest,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Compass8> {
+        Compass8::ALL.iter().cloned()
+    }
+
+    /// The number of 45 degree steps clockwise from `North`.
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Rotate by `eighths` 45 degree steps, clockwise for positive values.
+    pub fn rotate(self, eighths: i32) -> Compass8 {
+        Compass8::ALL[(self.index() as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// Turn 90 degrees, or not at all, or around.
+    pub fn turn(self, turn: Turn) -> Compass8 {
+        match turn {
+            Turn::Left => self.rotate(-2),
+            Turn::Right => self.rotate(2),
+            Turn::Straight => self,
+            Turn::Back => self.rotate(4),
+        }
+    }
+
+    /// Turn 45 degrees to the left or right, or not at all, or around.
+    pub fn turn45(self, turn: Turn) -> Compass8 {
+        match turn {
+            Turn::Left => self.rotate(-1),
+            Turn::Right => self.rotate(1),
+            Turn::Straight => self,
+            Turn::Back => self.rotate(4),
+        }
+    }
+
+    pub fn opposite(self) -> Compass8 {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// The unit step in this direction, with y growing downwards.
+    pub fn offset(self) -> Position {
+        use crate::position::Compass8::*;
+        match self {
+            North => Position { x: 0, y: -1 },
+            NorthEast => Position { x: 1, y: -1 },
+            East => Position { x: 1, y: 0 },
+            SouthEast => Position { x: 1, y: 1 },
+            South => Position { x: 0, y: 1 },
+            SouthWest => Position { x: -1, y: 1 },
+            West => Position { x: -1, y: 0 },
+            NorthWest => Position { x: -1, y: -1 },
+        }
+    }
+}
+
+impl Step<Compass8> for Position {
+    fn step(&self, direction: Compass8) -> Self {
+        self + direction.offset()
+    }
+}
+
+impl From<Cardinal> for Compass8 {
+    fn from(cardinal: Cardinal) -> Self {
+        match cardinal {
+            Cardinal::North => Compass8::North,
+            Cardinal::South => Compass8::South,
+            Cardinal::East => Compass8::East,
+            Cardinal::West => Compass8::West,
+        }
+    }
+}
+
+impl From<Direction> for Compass8 {
+    fn from(direction: Direction) -> Self {
+        Compass8::from(Cardinal::from(direction))
+    }
+}
+
+impl TryFrom<Compass8> for Cardinal {
+    type Error = Error;
+
+    fn try_from(compass: Compass8) -> Result<Self, Error> {
+        match compass {
+            Compass8::North => Ok(Cardinal::North),
+            Compass8::South => Ok(Cardinal::South),
+            Compass8::East => Ok(Cardinal::East),
+            Compass8::West => Ok(Cardinal::West),
+            diagonal => bail!("{:?} is not a cardinal direction", diagonal),
+        }
+    }
+}
+
+impl TryFrom<Compass8> for Direction {
+    type Error = Error;
+
+    fn try_from(compass: Compass8) -> Result<Self, Error> {
+        Cardinal::try_from(compass).map(Direction::from)
+    }
+}
+
 pub fn connect<P>(position: P) -> impl Iterator<Item = Position>
 where
     P: Into<Position>,
@@ -217,16 +776,220 @@ pub fn connect8<P>(position: P) -> impl Iterator<Item = Position>
 where
     P: Into<Position>,
 {
-    static OFFSETS: [Position; 8] = [
-        Position { x: 0, y: -1 },
-        Position { x: 1, y: -1 },
-        Position { x: 1, y: 0 },
-        Position { x: 1, y: 1 },
-        Position { x: 0, y: 1 },
-        Position { x: -1, y: 1 },
-        Position { x: -1, y: 0 },
-        Position { x: -1, y: -1 },
-    ];
     let position: Position = position.into();
-    OFFSETS.iter().map(move |&offset| position + offset)
+    Compass8::all().map(move |direction| position.step(direction))
+}
+
+/// The axis-aligned neighbours of a point, two per dimension.
+///
+/// For `Position` this gives the same points as `connect`.
+pub fn neighbours<P>(point: P) -> impl Iterator<Item = P>
+where
+    P: Point,
+{
+    (0..2 * P::DIMENSIONS).map(move |index| {
+        let (axis, delta) = (index / 2, if index % 2 == 0 { -1 } else { 1 });
+        P::from_fn(|a| point.coordinate(a) + if a == axis { delta } else { 0 })
+    })
+}
+
+/// All points at Chebyshev distance one from a point, 3^N - 1 in total.
+///
+/// For `Position` this gives the same points as `connect8`.
+pub fn neighbours_diagonal<P>(point: P) -> impl Iterator<Item = P>
+where
+    P: Point,
+{
+    let count = 3usize.pow(P::DIMENSIONS as u32);
+    (0..count)
+        .filter(move |&index| index != count / 2)
+        .map(move |index| {
+            P::from_fn(|axis| {
+                let digit = (index / 3usize.pow(axis as u32)) % 3;
+                point.coordinate(axis) + digit as Scalar - 1
+            })
+        })
+}
+
+/// The inclusive lower and upper corners of the smallest box containing all points.
+pub fn bounding_box<P, I>(points: I) -> Option<(P, P)>
+where
+    P: Point,
+    I: IntoIterator<Item = P>,
+{
+    let mut points = points.into_iter();
+    let first = points.next()?;
+    Some(points.fold((first, first), |(low, high), point| {
+        (
+            P::from_fn(|axis| min(low.coordinate(axis), point.coordinate(axis))),
+            P::from_fn(|axis| max(high.coordinate(axis), point.coordinate(axis))),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hashbrown::HashSet;
+
+    #[test]
+    fn test_distances() {
+        let a = Position3::new(0, 0, 0);
+        let b = Position3::new(1, -3, 2);
+        assert_eq!(a.manhattan(&b), 6);
+        assert_eq!(a.chebyshev(&b), 3);
+        assert_eq!(Position::new(1, 6).manhattan(&Position::new(8, 3)), 10);
+        assert_eq!(
+            Position4::new(0, 0, 0, 0).manhattan(&(3, 0, -2, 1).into()),
+            6
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Position4::new(1, 2, 3, 4);
+        let b = Position4::new(-1, 0, 1, 2);
+        assert_eq!(a + b, Position4::new(0, 2, 4, 6));
+        assert_eq!(a - b, Position4::new(2, 2, 2, 2));
+        assert_eq!(-a, Position4::new(-1, -2, -3, -4));
+        assert_eq!(a * 2, Position4::new(2, 4, 6, 8));
+    }
+
+    #[test]
+    fn test_position_arithmetic() {
+        let mut a = Position::new(3, -4);
+        let b = Position::new(1, 2);
+        assert_eq!(a - b, Position::new(2, -6));
+        assert_eq!(-a, Position::new(-3, 4));
+        assert_eq!(a * 3, Position::new(9, -12));
+        assert_eq!(a.signum(), Position::new(1, -1));
+        assert_eq!(a.abs(), Position::new(3, 4));
+        a += b;
+        assert_eq!(a, Position::new(4, -2));
+        a -= b * 2;
+        assert_eq!(a, Position::new(2, -6));
+        assert_eq!(a.chebyshev(&Position::origin()), 6);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let north = Position::new(0, -1);
+        let east = Position::new(1, 0);
+        assert_eq!(north.rotate(Turn::Right), east);
+        assert_eq!(east.rotate(Turn::Left), north);
+        let point = Position::new(5, 2);
+        let pivot = Position::new(3, 1);
+        assert_eq!(point.rotate_around(pivot, Turn::Right), Position::new(2, 3));
+        let back = (0..4).fold(point, |p, _| p.rotate_around(pivot, Turn::Left));
+        assert_eq!(back, point);
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let upper_right = Position::new(5, 0);
+        let lower_left = Position::new(0, 1);
+        assert_eq!(upper_right.cmp_reading_order(&lower_left), Ordering::Less);
+        assert_eq!(upper_right.cmp(&lower_left), Ordering::Greater);
+        assert!(ReadingOrder(upper_right) < ReadingOrder(lower_left));
+
+        let mut positions = vec![lower_left, Position::new(2, 1), upper_right];
+        sort_reading_order(&mut positions, |&p| p);
+        assert_eq!(
+            positions,
+            vec![upper_right, lower_left, Position::new(2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::from_points(vec![(3, 1), (-1, 4), (0, 2)]).unwrap();
+        assert_eq!(rect, Rect::new(Position::new(3, 4), Position::new(-1, 1)));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (5, 4, 20));
+        assert_eq!(rect.positions().count(), rect.area());
+        assert_eq!(rect.positions().next(), Some(Position::new(-1, 1)));
+        assert_eq!(rect.positions().last(), Some(Position::new(3, 4)));
+        assert!(rect.contains(Position::new(3, 4)));
+        assert!(!rect.contains(Position::new(4, 4)));
+
+        let other = Rect::new(Position::new(2, 0), Position::new(6, 1));
+        assert_eq!(
+            rect.intersection(&other),
+            Some(Rect::new(Position::new(2, 1), Position::new(3, 1)))
+        );
+        assert_eq!(
+            rect.union(&other),
+            Rect::new(Position::new(-1, 0), Position::new(6, 4))
+        );
+        let far = Rect::new(Position::new(10, 10), Position::new(11, 11));
+        assert_eq!(rect.intersection(&far), None);
+        assert_eq!(
+            rect.expand(2),
+            Rect::new(Position::new(-3, -1), Position::new(5, 6))
+        );
+        assert_eq!(Rect::from_origo(3, 2).area(), 6);
+    }
+
+    #[test]
+    fn test_compass8() {
+        use crate::position::Compass8::*;
+        assert_eq!(North.turn45(Turn::Right), NorthEast);
+        assert_eq!(North.turn(Turn::Left), West);
+        assert_eq!(SouthWest.turn(Turn::Back), NorthEast);
+        assert_eq!(NorthWest.rotate(1), North);
+        assert_eq!(East.opposite(), West);
+        assert_eq!(Compass8::all().filter(|d| d.is_diagonal()).count(), 4);
+        assert!(Compass8::all().all(|d| d.offset() == -d.opposite().offset()));
+        assert_eq!(Position::new(2, 2).step(SouthEast), Position::new(3, 3));
+
+        for &cardinal in &[
+            Cardinal::North,
+            Cardinal::South,
+            Cardinal::East,
+            Cardinal::West,
+        ] {
+            let compass = Compass8::from(cardinal);
+            assert_eq!(Cardinal::try_from(compass).unwrap(), cardinal);
+            assert_eq!(compass.turn(Turn::Right), cardinal.turn(Turn::Right).into());
+            let position = Position::new(0, 0);
+            assert_eq!(position.step(compass), position.step(cardinal));
+        }
+        assert!(Direction::try_from(NorthEast).is_err());
+        assert_eq!(Direction::Up.turn(Turn::Back), Direction::Down);
+        assert_eq!(Cardinal::East.turn(Turn::Straight), Cardinal::East);
+    }
+
+    #[test]
+    fn test_neighbours_match_connect() {
+        let position = Position::new(3, -2);
+        let expected = connect(position).collect::<HashSet<_>>();
+        assert_eq!(neighbours(position).collect::<HashSet<_>>(), expected);
+        let expected = connect8(position).collect::<HashSet<_>>();
+        assert_eq!(
+            neighbours_diagonal(position).collect::<HashSet<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_neighbour_counts() {
+        let origin = Position4::origin();
+        assert_eq!(neighbours(origin).count(), 8);
+        assert_eq!(neighbours_diagonal(origin).count(), 80);
+        assert!(neighbours_diagonal(origin).all(|p| origin.chebyshev(&p) == 1));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [
+            Position3::new(1, 5, -2),
+            Position3::new(-3, 2, 0),
+            Position3::new(4, 0, 7),
+        ];
+        let (low, high) = bounding_box(points.iter().cloned()).unwrap();
+        assert_eq!(low, Position3::new(-3, 0, -2));
+        assert_eq!(high, Position3::new(4, 5, 7));
+        assert!(points.iter().all(|p| p.within(&low, &high)));
+        assert!(!Position3::new(5, 0, 0).within(&low, &high));
+        assert_eq!(bounding_box(Vec::<Position>::new()), None);
+    }
 }
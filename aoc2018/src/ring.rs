@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+use std::iter::FromIterator;
+
+/// A circle of values with a current position, where inserting, removing and moving a step are
+/// all constant time.
+///
+/// The current value is kept at the back of a `VecDeque`, so that the front is the next value
+/// clockwise. Rotating moves values from one end to the other.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Ring<T> {
+    values: VecDeque<T>,
+}
+
+impl<T> Ring<T> {
+    pub fn new() -> Ring<T> {
+        Ring {
+            values: VecDeque::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.values.back()
+    }
+
+    /// Move the current position `steps` values clockwise, or counter-clockwise if negative.
+    pub fn rotate(&mut self, steps: i64) {
+        if self.values.is_empty() {
+            return;
+        }
+        let steps = steps.rem_euclid(self.values.len() as i64) as usize;
+        if steps <= self.values.len() / 2 {
+            self.values.rotate_left(steps);
+        } else {
+            self.values.rotate_right(self.values.len() - steps);
+        }
+    }
+
+    /// Insert a value clockwise of the current value, and make it the current value.
+    pub fn insert(&mut self, value: T) {
+        self.values.push_back(value);
+    }
+
+    /// Remove the current value, making the value clockwise of it current.
+    pub fn remove(&mut self) -> Option<T> {
+        let value = self.values.pop_back()?;
+        if let Some(next) = self.values.pop_front() {
+            self.values.push_back(next);
+        }
+        Some(value)
+    }
+
+    /// The values clockwise from the current value, starting with it.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let rest = self.values.len().saturating_sub(1);
+        self.values
+            .back()
+            .into_iter()
+            .chain(self.values.range(..rest))
+    }
+}
+
+impl<T> Default for Ring<T> {
+    fn default() -> Self {
+        Ring::new()
+    }
+}
+
+impl<T> FromIterator<T> for Ring<T> {
+    /// A ring of values in clockwise order, with the first value current.
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut values = values.into_iter().collect::<VecDeque<_>>();
+        if let Some(first) = values.pop_front() {
+            values.push_back(first);
+        }
+        Ring { values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(ring: &Ring<i32>) -> Vec<i32> {
+        ring.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut ring = (0..5).collect::<Ring<_>>();
+        assert_eq!(ring.current(), Some(&0));
+        assert_eq!(contents(&ring), vec![0, 1, 2, 3, 4]);
+        ring.rotate(2);
+        assert_eq!(contents(&ring), vec![2, 3, 4, 0, 1]);
+        ring.rotate(-3);
+        assert_eq!(contents(&ring), vec![4, 0, 1, 2, 3]);
+        ring.rotate(-12);
+        assert_eq!(contents(&ring), vec![2, 3, 4, 0, 1]);
+        ring.rotate(15);
+        assert_eq!(contents(&ring), vec![2, 3, 4, 0, 1]);
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut ring = Ring::new();
+        assert_eq!(ring.remove(), None);
+        ring.rotate(3);
+        ring.insert(1);
+        ring.insert(2);
+        ring.insert(3);
+        assert_eq!(contents(&ring), vec![3, 1, 2]);
+        ring.rotate(-1);
+        ring.insert(4);
+        assert_eq!(contents(&ring), vec![4, 3, 1, 2]);
+        assert_eq!(ring.remove(), Some(4));
+        assert_eq!(contents(&ring), vec![3, 1, 2]);
+        assert_eq!(ring.remove(), Some(3));
+        assert_eq!(ring.remove(), Some(1));
+        assert_eq!(contents(&ring), vec![2]);
+        assert_eq!(ring.remove(), Some(2));
+        assert!(ring.is_empty());
+        assert_eq!(ring.current(), None);
+    }
+}
@@ -0,0 +1,113 @@
+use hashbrown::HashMap;
+use itertools::Itertools;
+
+/// The number of positions where two strings have different characters.
+///
+/// Strings of different lengths in characters have no Hamming distance.
+pub fn hamming_distance(a: &str, b: &str) -> Option<usize> {
+    if a.chars().count() != b.chars().count() {
+        return None;
+    }
+    Some(a.chars().zip(b.chars()).filter(|(x, y)| x != y).count())
+}
+
+/// The characters that are the same at the same position in both strings.
+pub fn common_chars(a: &str, b: &str) -> String {
+    a.chars()
+        .zip(b.chars())
+        .filter(|(x, y)| x == y)
+        .map(|(x, _)| x)
+        .collect()
+}
+
+/// All pairs of indices of strings at Hamming distance exactly `k`, smallest indices first.
+///
+/// Only strings of the same length are compared. Two strings are at distance exactly `k` only if
+/// they are equal after deleting the `k` positions where they differ, so each string is hashed
+/// once for every way of deleting `k` positions, and only strings with the same key are compared.
+/// This is fast for small `k`, as each string has `len` choose `k` keys.
+pub fn pairs_at_distance<S>(strings: &[S], k: usize) -> Vec<(usize, usize)>
+where
+    S: AsRef<str>,
+{
+    let strings = strings
+        .iter()
+        .map(|s| s.as_ref().chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut buckets: HashMap<(Vec<usize>, Vec<char>), Vec<usize>> = HashMap::new();
+    for (index, chars) in strings.iter().enumerate() {
+        if k > chars.len() {
+            continue;
+        }
+        for deleted in (0..chars.len()).combinations(k) {
+            let mut kept = Vec::with_capacity(chars.len() - k);
+            let mut deleted_iter = deleted.iter().peekable();
+            for (position, &ch) in chars.iter().enumerate() {
+                if deleted_iter.peek() == Some(&&position) {
+                    deleted_iter.next();
+                } else {
+                    kept.push(ch);
+                }
+            }
+            buckets.entry((deleted, kept)).or_default().push(index);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for members in buckets.values() {
+        for (i, &a) in members.iter().enumerate() {
+            for &b in members[i + 1..].iter() {
+                let distance = strings[a]
+                    .iter()
+                    .zip(strings[b].iter())
+                    .filter(|(x, y)| x != y)
+                    .count();
+                if distance == k {
+                    pairs.push((a, b));
+                }
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hamming_distance() {
+        assert_eq!(hamming_distance("fghij", "fguij"), Some(1));
+        assert_eq!(hamming_distance("abcde", "axcye"), Some(2));
+        assert_eq!(hamming_distance("abc", "abc"), Some(0));
+        assert_eq!(hamming_distance("abc", "abcd"), None);
+        assert_eq!(hamming_distance("åäö", "aäö"), Some(1));
+        assert_eq!(common_chars("fghij", "fguij"), "fgij");
+    }
+
+    #[test]
+    fn test_pairs_at_distance() {
+        let strings = [
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz", "abcde", "fgh",
+        ];
+        let brute_force = |k| {
+            let mut pairs = Vec::new();
+            for a in 0..strings.len() {
+                for b in a + 1..strings.len() {
+                    if hamming_distance(strings[a], strings[b]) == Some(k) {
+                        pairs.push((a, b));
+                    }
+                }
+            }
+            pairs
+        };
+        assert_eq!(pairs_at_distance(&strings, 0), vec![(0, 7)]);
+        assert_eq!(pairs_at_distance(&strings, 1), vec![(1, 4)]);
+        assert_eq!(pairs_at_distance(&strings, 2), vec![(0, 5), (5, 7)]);
+        for k in 0..=6 {
+            assert_eq!(pairs_at_distance(&strings, k), brute_force(k), "k = {}", k);
+        }
+    }
+}
@@ -1,7 +1,5 @@
 use crate::position::*;
 use hashbrown::HashMap;
-use std::cmp::max;
-use std::cmp::min;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -14,10 +12,8 @@ where
     T: Debug + Clone + Eq,
 {
     pub values: HashMap<Position, T>,
-    pub min_x: Scalar,
-    pub min_y: Scalar,
-    pub max_x: Scalar,
-    pub max_y: Scalar,
+    /// The smallest rectangle containing all inserted positions, if any.
+    pub bounds: Option<Rect>,
 }
 
 impl<T> Grid<T>
@@ -27,34 +23,27 @@ where
     pub fn new() -> Grid<T> {
         Grid {
             values: HashMap::with_capacity(256),
-            min_x: 0,
-            min_y: 0,
-            max_x: 0,
-            max_y: 0,
+            bounds: None,
         }
     }
 
-    pub fn filled(
-        value: &T,
-        min_x: Scalar,
-        min_y: Scalar,
-        max_x: Scalar,
-        max_y: Scalar,
-    ) -> Grid<T> {
+    /// A grid with `value` at every position in `bounds`.
+    ///
+    /// The bounds are inclusive like all `Rect`s, so the positions on the edges of `bounds`,
+    /// including `bounds.max`, are filled too.
+    pub fn filled(value: &T, bounds: Rect) -> Grid<T> {
         let mut grid = Grid::new();
-        for x in min_x..max_x {
-            for y in min_y..max_y {
-                grid.insert((x, y).into(), value.clone());
-            }
+        for position in bounds.positions() {
+            grid.insert(position, value.clone());
         }
         grid
     }
 
     fn update_bounds(&mut self, position: Position) {
-        self.min_x = min(self.min_x, position.x);
-        self.min_y = min(self.min_y, position.y);
-        self.max_x = max(self.max_x, position.x);
-        self.max_y = max(self.max_y, position.y);
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.include(position),
+            None => Rect::new(position, position),
+        });
     }
 
     pub fn insert(&mut self, position: Position, value: T) {
@@ -65,6 +54,20 @@ where
     pub fn get(&self, position: Position) -> Option<&T> {
         self.values.get(&position)
     }
+
+    /// The positions that have values, in reading order.
+    pub fn positions(&self) -> Vec<Position> {
+        let mut positions = self.values.keys().cloned().collect::<Vec<_>>();
+        sort_reading_order(&mut positions, |&position| position);
+        positions
+    }
+
+    /// The positions that have values together with the values, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions()
+            .into_iter()
+            .map(move |position| (position, &self.values[&position]))
+    }
 }
 
 impl<T> Default for Grid<T>
@@ -148,16 +151,47 @@ where
             .max()
             .unwrap_or(1);
         let filler = " ".repeat(width);
-        for y in self.min_y..self.max_y {
-            for x in self.min_x..self.max_x {
-                if let Some(v) = self.get((x, y).into()) {
+        if let Some(bounds) = self.bounds {
+            for position in bounds.positions() {
+                if let Some(v) = self.get(position) {
                     write!(f, "{:width$}", v, width = width)?;
                 } else {
                     write!(f, "{}", filler)?;
                 }
+                if position.x == bounds.max.x {
+                    writeln!(f)?;
+                }
             }
-            writeln!(f)?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filled_includes_corners() {
+        let bounds = Rect::new((1, 2).into(), (3, 5).into());
+        let grid = Grid::filled(&'#', bounds);
+        assert_eq!(grid.values.len(), 12);
+        assert_eq!(grid.bounds, Some(bounds));
+        for &corner in [(1, 2), (3, 2), (1, 5), (3, 5)].iter() {
+            assert_eq!(grid.get(corner.into()), Some(&'#'), "{:?}", corner);
+        }
+        for &outside in [(0, 2), (4, 5), (1, 1), (3, 6)].iter() {
+            assert_eq!(grid.get(outside.into()), None, "{:?}", outside);
+        }
+    }
+
+    #[test]
+    fn test_display_away_from_origin() {
+        let mut grid = Grid::new();
+        grid.insert((2, 3).into(), 1);
+        grid.insert((4, 4).into(), 2);
+        assert_eq!(grid.bounds, Some(Rect::new((2, 3).into(), (4, 4).into())));
+        assert_eq!(grid.to_string(), "1  \n  2\n");
+        assert_eq!(Grid::<i32>::new().to_string(), "");
+    }
+}
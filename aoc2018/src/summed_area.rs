@@ -0,0 +1,314 @@
+use crate::dense_grid::Grid;
+use crate::position::*;
+use num::Num;
+use std::fmt::Debug;
+
+/// Cumulative sums over a rectangle, giving the sum of any sub-rectangle in constant time.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SummedAreaTable<T>
+where
+    T: Num + Copy,
+{
+    bounds: Rect,
+    /// Sums of all values above and to the left, with an extra leading row and column of zeros.
+    sums: Vec<T>,
+}
+
+impl<T> SummedAreaTable<T>
+where
+    T: Num + Copy,
+{
+    pub fn from_fn<F>(bounds: Rect, mut value: F) -> SummedAreaTable<T>
+    where
+        F: FnMut(Position) -> T,
+    {
+        let stride = bounds.width() + 1;
+        let mut sums = vec![T::zero(); stride * (bounds.height() + 1)];
+        for position in bounds.positions() {
+            let offset = position - bounds.min;
+            let (x, y) = (offset.x as usize + 1, offset.y as usize + 1);
+            sums[y * stride + x] =
+                value(position) + sums[(y - 1) * stride + x] + sums[y * stride + x - 1]
+                    - sums[(y - 1) * stride + x - 1];
+        }
+        SummedAreaTable { bounds, sums }
+    }
+
+    pub fn from_dense_grid(grid: &Grid<T>) -> SummedAreaTable<T>
+    where
+        T: Debug + Eq + Default,
+    {
+        SummedAreaTable::from_fn(grid.bounds, |position| grid[position])
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// The sum of all values up to but not including `x` and `y` in table coordinates.
+    fn prefix(&self, x: usize, y: usize) -> T {
+        self.sums[y * (self.bounds.width() + 1) + x]
+    }
+
+    /// The sum of the values in `rect`, ignoring any part outside the bounds.
+    pub fn sum(&self, rect: Rect) -> T {
+        match rect.intersection(&self.bounds) {
+            Some(rect) => {
+                let min = rect.min - self.bounds.min;
+                let max = rect.max - self.bounds.min;
+                let (x1, y1) = (min.x as usize, min.y as usize);
+                let (x2, y2) = (max.x as usize + 1, max.y as usize + 1);
+                self.prefix(x2, y2) - self.prefix(x1, y2) - self.prefix(x2, y1)
+                    + self.prefix(x1, y1)
+            }
+            None => T::zero(),
+        }
+    }
+
+    /// The sums of all `size` by `size` squares inside the bounds, by top left corner in
+    /// reading order.
+    pub fn square_sums(&self, size: usize) -> impl Iterator<Item = (Position, T)> + '_ {
+        let fits = size > 0 && size <= self.bounds.width() && size <= self.bounds.height();
+        let last = self.bounds.max - Position::new(size as Scalar - 1, size as Scalar - 1);
+        let corners = if fits {
+            Some(Rect::new(self.bounds.min, last))
+        } else {
+            None
+        };
+        corners
+            .into_iter()
+            .flat_map(|corners| corners.positions())
+            .map(move |corner| (corner, self.sum(square(corner, size))))
+    }
+
+    /// The `size` by `size` square with the largest sum, preferring the first in reading order.
+    pub fn best_square(&self, size: usize) -> Option<(Position, T)>
+    where
+        T: PartialOrd,
+    {
+        let mut best: Option<(Position, T)> = None;
+        for (corner, sum) in self.square_sums(size) {
+            if best.is_none_or(|(_, best_sum)| sum > best_sum) {
+                best = Some((corner, sum));
+            }
+        }
+        best
+    }
+
+    /// The square with the largest sum over all the given sizes, as corner, size and sum.
+    ///
+    /// Ties are broken in favour of the earliest size, then the first corner in reading order.
+    pub fn best_square_of_sizes<I>(&self, sizes: I) -> Option<(Position, usize, T)>
+    where
+        T: PartialOrd,
+        I: IntoIterator<Item = usize>,
+    {
+        let mut best: Option<(Position, usize, T)> = None;
+        for size in sizes {
+            if let Some((corner, sum)) = self.best_square(size) {
+                if best.is_none_or(|(_, _, best_sum)| sum > best_sum) {
+                    best = Some((corner, size, sum));
+                }
+            }
+        }
+        best
+    }
+}
+
+/// The `size` by `size` square with top left corner at `corner`.
+fn square(corner: Position, size: usize) -> Rect {
+    let size = size as Scalar;
+    Rect::new(corner, corner + Position::new(size - 1, size - 1))
+}
+
+/// Collects increments of rectangles, and adds them all up in a single pass at the end.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DifferenceArray<T>
+where
+    T: Num + Copy,
+{
+    bounds: Rect,
+    /// Differences with an extra trailing row and column for the ends of rectangles.
+    differences: Vec<T>,
+}
+
+impl<T> DifferenceArray<T>
+where
+    T: Num + Copy,
+{
+    pub fn new(bounds: Rect) -> DifferenceArray<T> {
+        let size = (bounds.width() + 1) * (bounds.height() + 1);
+        DifferenceArray {
+            bounds,
+            differences: vec![T::zero(); size],
+        }
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// Add `value` to all positions in `rect`, ignoring any part outside the bounds.
+    pub fn add(&mut self, rect: Rect, value: T) {
+        if let Some(rect) = rect.intersection(&self.bounds) {
+            let stride = self.bounds.width() + 1;
+            let min = rect.min - self.bounds.min;
+            let max = rect.max - self.bounds.min;
+            let (x1, y1) = (min.x as usize, min.y as usize);
+            let (x2, y2) = (max.x as usize + 1, max.y as usize + 1);
+            let differences = &mut self.differences;
+            differences[y1 * stride + x1] = differences[y1 * stride + x1] + value;
+            differences[y1 * stride + x2] = differences[y1 * stride + x2] - value;
+            differences[y2 * stride + x1] = differences[y2 * stride + x1] - value;
+            differences[y2 * stride + x2] = differences[y2 * stride + x2] + value;
+        }
+    }
+
+    /// The total of all increments at each position.
+    pub fn into_grid(self) -> Grid<T>
+    where
+        T: Debug + Eq + Default,
+    {
+        let stride = self.bounds.width() + 1;
+        let mut sums = self.differences;
+        for y in 0..=self.bounds.height() {
+            for x in 0..stride {
+                let mut sum = sums[y * stride + x];
+                if x > 0 {
+                    sum = sum + sums[y * stride + x - 1];
+                }
+                if y > 0 {
+                    sum = sum + sums[(y - 1) * stride + x];
+                }
+                if x > 0 && y > 0 {
+                    sum = sum - sums[(y - 1) * stride + x - 1];
+                }
+                sums[y * stride + x] = sum;
+            }
+        }
+
+        let mut grid = Grid::from_bounds(self.bounds);
+        for position in self.bounds.positions() {
+            let offset = position - self.bounds.min;
+            grid[position] = sums[offset.y as usize * stride + offset.x as usize];
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(grid: &Grid<i64>, rect: Rect) -> i64 {
+        rect.positions()
+            .filter_map(|position| grid.get(position))
+            .sum()
+    }
+
+    fn sample() -> Grid<i64> {
+        let mut grid = Grid::new(-2, 1, 3, 5);
+        for (index, position) in grid.positions().collect::<Vec<_>>().into_iter().enumerate() {
+            grid[position] = (index as i64 * 7) % 11 - 5;
+        }
+        grid
+    }
+
+    #[test]
+    fn test_sum() {
+        let grid = sample();
+        let table = SummedAreaTable::from_dense_grid(&grid);
+        assert_eq!(table.bounds(), grid.bounds);
+        assert_eq!(table.sum(grid.bounds), grid.values.iter().sum());
+        for a in grid.bounds.expand(1).positions() {
+            for b in grid.bounds.expand(1).positions() {
+                let rect = Rect::new(a, b);
+                assert_eq!(table.sum(rect), brute_force(&grid, rect), "{}", rect);
+            }
+        }
+        assert_eq!(table.sum(Rect::new((10, 10).into(), (12, 12).into())), 0);
+    }
+
+    #[test]
+    fn test_best_square() {
+        let grid = sample();
+        let table = SummedAreaTable::from_dense_grid(&grid);
+        for size in 1..=5 {
+            let expected = grid
+                .positions()
+                .filter(|&corner| grid.bounds.contains(square(corner, size).max))
+                .map(|corner| (corner, brute_force(&grid, square(corner, size))))
+                .fold(
+                    None,
+                    |best: Option<(Position, i64)>, (corner, sum)| match best {
+                        Some((_, best_sum)) if best_sum >= sum => best,
+                        _ => Some((corner, sum)),
+                    },
+                );
+            assert_eq!(table.best_square(size), expected);
+            assert_eq!(
+                table.square_sums(size).count(),
+                (grid.width() + 1 - size) * (grid.height() + 1 - size)
+            );
+        }
+        assert_eq!(table.best_square(6), None);
+        assert_eq!(table.square_sums(0).count(), 0);
+
+        let (corner, size, sum) = table.best_square_of_sizes(1..=6).unwrap();
+        assert_eq!(table.best_square(size), Some((corner, sum)));
+        for other in 1..=5 {
+            assert!(table.best_square(other).unwrap().1 <= sum);
+        }
+    }
+
+    #[test]
+    fn test_difference_array() {
+        let bounds = Rect::new((1, 1).into(), (6, 5).into());
+        let rects = [
+            (Rect::new((1, 3).into(), (4, 6).into()), 1),
+            (Rect::new((3, 1).into(), (6, 4).into()), 2),
+            (Rect::new((5, 5).into(), (6, 6).into()), -3),
+            (Rect::new((-5, -5).into(), (0, 0).into()), 10),
+        ];
+        let mut differences = DifferenceArray::new(bounds);
+        for &(rect, value) in rects.iter() {
+            differences.add(rect, value);
+        }
+        let grid = differences.into_grid();
+        assert_eq!(grid.bounds, bounds);
+        for position in bounds.positions() {
+            let expected: i32 = rects
+                .iter()
+                .filter(|(rect, _)| rect.contains(position))
+                .map(|&(_, value)| value)
+                .sum();
+            assert_eq!(grid[position], expected, "{}", position);
+        }
+    }
+
+    #[test]
+    fn test_difference_array_matches_fill() {
+        let bounds = Rect::new((-3, 2).into(), (9, 10).into());
+        let mut state = 7u64;
+        let mut random = move |range: i32| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as i32 % range
+        };
+
+        let mut differences = DifferenceArray::new(bounds);
+        let mut filled: Grid<i64> = Grid::from_bounds(bounds);
+        for _ in 0..200 {
+            let a = Position::new(random(18) - 6, random(14) - 1);
+            let b = Position::new(random(18) - 6, random(14) - 1);
+            let rect = Rect::new(a, b);
+            let value = i64::from(random(21) - 10);
+            differences.add(rect, value);
+            for position in rect.positions().filter(|&p| bounds.contains(p)) {
+                filled[position] += value;
+            }
+        }
+        assert_eq!(differences.into_grid(), filled);
+    }
+}
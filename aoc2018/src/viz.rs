@@ -0,0 +1,338 @@
+use crate::dense_grid;
+use crate::export::Colour;
+use crate::position::*;
+use crate::sparse_grid;
+use failure::Error;
+use std::env;
+use std::fmt::{Debug, Write as FmtWrite};
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A single character of a terminal frame, with optional 24-bit colours.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub foreground: Option<Colour>,
+    pub background: Option<Colour>,
+}
+
+impl Cell {
+    pub fn new(glyph: char, foreground: Colour) -> Cell {
+        Cell {
+            glyph,
+            foreground: Some(foreground),
+            background: None,
+        }
+    }
+
+    pub fn plain(glyph: char) -> Cell {
+        Cell {
+            glyph,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    pub fn with_background(mut self, background: Colour) -> Cell {
+        self.background = Some(background);
+        self
+    }
+
+    fn colours(&self) -> (Option<Colour>, Option<Colour>) {
+        (self.foreground, self.background)
+    }
+}
+
+impl From<char> for Cell {
+    fn from(glyph: char) -> Cell {
+        Cell::plain(glyph)
+    }
+}
+
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const HOME: &str = "\x1b[H";
+const RESET: &str = "\x1b[0m";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+
+/// Redraws grids in place on a terminal using ANSI escape codes.
+///
+/// Only the part of the grid inside the viewport is drawn. When a focus is set, the
+/// viewport is moved to keep the focus centered, as far as the grid bounds allow.
+pub struct Animation<W: Write> {
+    out: W,
+    width: usize,
+    height: usize,
+    focus: Option<Position>,
+    frame_time: Option<Duration>,
+    last_frame: Option<Instant>,
+    frames: usize,
+}
+
+impl Animation<io::Stdout> {
+    /// An animation on standard output, sized by `$COLUMNS` and `$LINES` if set.
+    pub fn stdout() -> Animation<io::Stdout> {
+        let size = |name: &str, default: usize| {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+        // Leave one line for the status line.
+        let height = size("LINES", 25).max(2) - 1;
+        Animation::new(io::stdout()).with_viewport(size("COLUMNS", 80), height)
+    }
+}
+
+impl<W: Write> Animation<W> {
+    pub fn new(out: W) -> Animation<W> {
+        Animation {
+            out,
+            width: 80,
+            height: 24,
+            focus: None,
+            frame_time: None,
+            last_frame: None,
+            frames: 0,
+        }
+    }
+
+    pub fn with_viewport(mut self, width: usize, height: usize) -> Animation<W> {
+        assert!(width > 0 && height > 0, "The viewport must not be empty");
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Limit the animation to at most `fps` frames per second, with 0 meaning no limit.
+    pub fn with_fps(mut self, fps: u32) -> Animation<W> {
+        self.frame_time = if fps == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(1) / fps)
+        };
+        self
+    }
+
+    /// Keep `focus` centered in the viewport in the following frames.
+    pub fn follow(&mut self, focus: Position) {
+        self.focus = Some(focus);
+    }
+
+    pub fn unfollow(&mut self) {
+        self.focus = None;
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// The part of `bounds` that is visible.
+    pub fn viewport(&self, bounds: Rect) -> Rect {
+        fn start(min: Scalar, max: Scalar, size: usize, focus: Option<Scalar>) -> Scalar {
+            let size = size as Scalar;
+            match focus {
+                Some(focus) if max - min + 1 > size => {
+                    (focus - size / 2).max(min).min(max - size + 1)
+                }
+                _ => min,
+            }
+        }
+
+        let x = start(
+            bounds.min.x,
+            bounds.max.x,
+            self.width,
+            self.focus.map(|f| f.x),
+        );
+        let y = start(
+            bounds.min.y,
+            bounds.max.y,
+            self.height,
+            self.focus.map(|f| f.y),
+        );
+        let min = Position::new(x, y);
+        let max = Position::new(
+            (x + self.width as Scalar - 1).min(bounds.max.x),
+            (y + self.height as Scalar - 1).min(bounds.max.y),
+        );
+        Rect::new(min, max)
+    }
+
+    /// Draw the visible part of `bounds` followed by a status line.
+    pub fn draw<F>(&mut self, bounds: Rect, mut cell: F, status: &str) -> Result<(), Error>
+    where
+        F: FnMut(Position) -> Cell,
+    {
+        let viewport = self.viewport(bounds);
+        let mut frame = String::new();
+        if self.frames == 0 {
+            frame.push_str(HIDE_CURSOR);
+            frame.push_str(CLEAR_SCREEN);
+        }
+        frame.push_str(HOME);
+        for y in viewport.min.y..=viewport.max.y {
+            let mut colours = (None, None);
+            for x in viewport.min.x..=viewport.max.x {
+                let cell = cell(Position::new(x, y));
+                if cell.colours() != colours {
+                    push_colours(&mut frame, cell.foreground, cell.background)?;
+                    colours = cell.colours();
+                }
+                frame.push(cell.glyph);
+            }
+            frame.push_str(RESET);
+            frame.push_str(CLEAR_LINE);
+            frame.push('\n');
+        }
+        frame.push_str(status);
+        frame.push_str(CLEAR_LINE);
+        frame.push_str(CLEAR_BELOW);
+
+        self.throttle();
+        self.out.write_all(frame.as_bytes())?;
+        self.out.flush()?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    pub fn draw_dense_grid<T, F>(
+        &mut self,
+        grid: &dense_grid::Grid<T>,
+        mut cell: F,
+        status: &str,
+    ) -> Result<(), Error>
+    where
+        T: Debug + Clone + Eq + Default,
+        F: FnMut(&T) -> Cell,
+    {
+        self.draw(grid.bounds, |position| cell(&grid[position]), status)
+    }
+
+    /// Draw a sparse grid, using `background` for positions without values.
+    pub fn draw_sparse_grid<T, F>(
+        &mut self,
+        grid: &sparse_grid::Grid<T>,
+        background: Cell,
+        mut cell: F,
+        status: &str,
+    ) -> Result<(), Error>
+    where
+        T: Debug + Clone + Eq,
+        F: FnMut(&T) -> Cell,
+    {
+        match grid.bounds {
+            Some(bounds) => self.draw(
+                bounds,
+                |position| grid.get(position).map_or(background, &mut cell),
+                status,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Restore the terminal, leaving the last frame on screen.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        if self.frames > 0 {
+            writeln!(self.out, "{}{}", RESET, SHOW_CURSOR)?;
+            self.out.flush()?;
+        }
+        self.frames = 0;
+        self.last_frame = None;
+        Ok(())
+    }
+
+    fn throttle(&mut self) {
+        if let (Some(frame_time), Some(last_frame)) = (self.frame_time, self.last_frame) {
+            let elapsed = last_frame.elapsed();
+            if elapsed < frame_time {
+                thread::sleep(frame_time - elapsed);
+            }
+        }
+        self.last_frame = Some(Instant::now());
+    }
+}
+
+impl<W: Write> Drop for Animation<W> {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+fn push_colours(
+    frame: &mut String,
+    foreground: Option<Colour>,
+    background: Option<Colour>,
+) -> Result<(), Error> {
+    frame.push_str(RESET);
+    if let Some(Colour { r, g, b }) = foreground {
+        write!(frame, "\x1b[38;2;{};{};{}m", r, g, b)?;
+    }
+    if let Some(Colour { r, g, b }) = background {
+        write!(frame, "\x1b[48;2;{};{};{}m", r, g, b)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewport() {
+        let mut animation = Animation::new(Vec::new()).with_viewport(4, 3);
+        let bounds = Rect::from_origo(10, 10);
+        assert_eq!(
+            animation.viewport(bounds),
+            Rect::new((0, 0).into(), (3, 2).into())
+        );
+
+        animation.follow((5, 5).into());
+        assert_eq!(
+            animation.viewport(bounds),
+            Rect::new((3, 4).into(), (6, 6).into())
+        );
+
+        animation.follow((9, 0).into());
+        assert_eq!(
+            animation.viewport(bounds),
+            Rect::new((6, 0).into(), (9, 2).into())
+        );
+
+        let small = Rect::from_origo(2, 2);
+        assert_eq!(animation.viewport(small), small);
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut grid = dense_grid::Grid::from_origo(3, 2);
+        grid[(1, 1)] = true;
+        let mut out = Vec::new();
+        {
+            let mut animation = Animation::new(&mut out);
+            animation
+                .draw_dense_grid(
+                    &grid,
+                    |&on| {
+                        if on {
+                            Cell::new('#', Colour::WHITE)
+                        } else {
+                            '.'.into()
+                        }
+                    },
+                    "step 1",
+                )
+                .unwrap();
+            assert_eq!(animation.frames(), 1);
+        }
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with(HIDE_CURSOR));
+        assert!(text.contains("...\x1b[0m\x1b[K\n"));
+        assert!(text.contains(".\x1b[0m\x1b[38;2;255;255;255m#\x1b[0m.\x1b[0m"));
+        assert!(text.contains("step 1"));
+        assert!(text.ends_with("\x1b[?25h\n"));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::dense_grid::Grid;
+use crate::position::*;
+
+/// The nearest seed of every position in a rectangle by Manhattan distance.
+///
+/// Computed with a breadth-first search from all seeds at once, one distance at a time. A
+/// position reached from neighbours with different nearest seeds, or from a neighbour that is
+/// itself tied, is equally close to several seeds and has no nearest seed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Voronoi {
+    pub seeds: Vec<Position>,
+    /// The index of the nearest seed, or `None` for ties.
+    pub nearest: Grid<Option<usize>>,
+    distances: Grid<Option<usize>>,
+}
+
+impl Voronoi {
+    /// The regions inside the bounding box of the seeds, or `None` if there are no seeds.
+    pub fn new(seeds: &[Position]) -> Option<Voronoi> {
+        let bounds = Rect::from_points(seeds.iter().cloned())?;
+        Some(Voronoi::with_bounds(seeds, bounds))
+    }
+
+    /// The regions inside `bounds`, which must contain all the seeds.
+    pub fn with_bounds(seeds: &[Position], bounds: Rect) -> Voronoi {
+        let mut nearest = Grid::from_bounds(bounds);
+        let mut distances: Grid<Option<usize>> = Grid::from_bounds(bounds);
+
+        let mut frontier = Vec::new();
+        for (index, &seed) in seeds.iter().enumerate() {
+            assert!(
+                bounds.contains(seed),
+                "Seed {} is outside the bounds {}",
+                seed,
+                bounds
+            );
+            if distances[seed].is_some() {
+                nearest[seed] = None;
+            } else {
+                distances[seed] = Some(0);
+                nearest[seed] = Some(index);
+                frontier.push(seed);
+            }
+        }
+
+        let mut distance = 0;
+        while !frontier.is_empty() {
+            distance += 1;
+            let mut next = Vec::new();
+            for position in frontier {
+                let label = nearest[position];
+                for neighbour in neighbours(position) {
+                    match distances.get(neighbour) {
+                        Some(None) => {
+                            distances[neighbour] = Some(distance);
+                            nearest[neighbour] = label;
+                            next.push(neighbour);
+                        }
+                        Some(&Some(reached))
+                            if reached == distance && nearest[neighbour] != label =>
+                        {
+                            nearest[neighbour] = None;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        Voronoi {
+            seeds: seeds.to_vec(),
+            nearest,
+            distances,
+        }
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.nearest.bounds
+    }
+
+    /// The distance from a position to its nearest seeds.
+    pub fn distance(&self, position: Position) -> Option<usize> {
+        self.distances.get(position).cloned().flatten()
+    }
+
+    /// The number of positions in the bounds that are nearest to each seed.
+    pub fn region_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.seeds.len()];
+        for &index in self.nearest.values.iter().flatten() {
+            sizes[index] += 1;
+        }
+        sizes
+    }
+
+    /// Which seeds have regions that extend infinitely outside the bounds.
+    ///
+    /// Outside the bounds, moving away from them increases the distance to every seed by the
+    /// same amount, so a position outside has the same nearest seed as the closest position on
+    /// the border. A region is thus unbounded exactly when it reaches the border.
+    pub fn unbounded(&self) -> Vec<bool> {
+        let bounds = self.bounds();
+        let mut unbounded = vec![false; self.seeds.len()];
+        for position in bounds.positions() {
+            let on_border = position.x == bounds.min.x
+                || position.x == bounds.max.x
+                || position.y == bounds.min.y
+                || position.y == bounds.max.y;
+            if let (true, Some(index)) = (on_border, self.nearest[position]) {
+                unbounded[index] = true;
+            }
+        }
+        unbounded
+    }
+
+    /// The seed with the largest bounded region and the size of that region.
+    pub fn largest_bounded_region(&self) -> Option<(usize, usize)> {
+        let unbounded = self.unbounded();
+        self.region_sizes()
+            .into_iter()
+            .enumerate()
+            .filter(|&(index, _)| !unbounded[index])
+            .max_by_key(|&(index, size)| (size, std::cmp::Reverse(index)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Position> {
+        [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]
+            .iter()
+            .map(|&p| p.into())
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let seeds = sample();
+        let voronoi = Voronoi::with_bounds(&seeds, Rect::new((-3, -2).into(), (12, 11).into()));
+        for position in voronoi.bounds().positions() {
+            let distances = seeds
+                .iter()
+                .map(|seed| seed.manhattan(&position) as usize)
+                .collect::<Vec<_>>();
+            let min = *distances.iter().min().unwrap();
+            let closest = (0..seeds.len())
+                .filter(|&i| distances[i] == min)
+                .collect::<Vec<_>>();
+            let expected = if closest.len() == 1 {
+                Some(closest[0])
+            } else {
+                None
+            };
+            assert_eq!(voronoi.nearest[position], expected, "{}", position);
+            assert_eq!(voronoi.distance(position), Some(min));
+        }
+    }
+
+    #[test]
+    fn test_regions() {
+        let voronoi = Voronoi::new(&sample()).unwrap();
+        assert_eq!(
+            voronoi.unbounded(),
+            vec![true, true, true, false, false, true]
+        );
+        assert_eq!(voronoi.largest_bounded_region(), Some((4, 17)));
+        assert_eq!(voronoi.region_sizes()[3], 9);
+        assert!(Voronoi::new(&[]).is_none());
+
+        let same = [Position::new(2, 2), Position::new(2, 2)];
+        let voronoi = Voronoi::new(&same).unwrap();
+        assert_eq!(voronoi.nearest[(2, 2)], None);
+    }
+}
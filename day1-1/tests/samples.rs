@@ -1,23 +0,0 @@
-use assert_cli;
-
-#[test]
-fn sample1() {
-    assert_cli::Assert::main_binary()
-        .stdin(
-            "1
-2
-3",
-        )
-        .stdout()
-        .is("6")
-        .unwrap();
-}
-
-#[test]
-fn puzzle1() {
-    assert_cli::Assert::main_binary()
-        .stdin(include_str!("../data/puzzle1.in"))
-        .stdout()
-        .is("427")
-        .unwrap();
-}
@@ -0,0 +1,212 @@
+#![allow(dead_code, unused_imports)]
+
+use failure::bail;
+use failure::format_err;
+use failure::Error;
+use rayon::prelude::*;
+use structopt::StructOpt;
+use strum_macros::EnumString;
+
+use hashbrown::{HashMap, HashSet};
+use std::char;
+use std::cmp::{max, min};
+use std::fmt;
+use std::io::BufRead;
+use std::ops::*;
+use std::str;
+use std::str::FromStr;
+use std::{io, process};
+
+type Input = Vec<i32>;
+type Output = String;
+
+fn read_input() -> Result<Input, Error> {
+    let stdin = io::stdin();
+    let mut result = Vec::new();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        result.push(line.parse()?)
+    }
+
+    Ok(result)
+}
+
+/// The first frequency that is reached twice, and when it is reached the second time.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Repeat {
+    frequency: i64,
+    /// The number of changes applied before the frequency repeats.
+    changes: usize,
+    /// The pass over the changes the repeat happens in, starting from 1.
+    pass: usize,
+    /// The line of the change that gives the repeat, starting from 1.
+    line: usize,
+}
+
+impl Repeat {
+    fn new(frequency: i64, changes: usize, length: usize) -> Repeat {
+        Repeat {
+            frequency,
+            changes,
+            pass: (changes - 1) / length + 1,
+            line: (changes - 1) % length + 1,
+        }
+    }
+}
+
+/// Find the first repeated frequency without simulating the passes.
+///
+/// After `k` full passes plus `i` more changes the frequency is `prefix[i] + k * drift`, where
+/// `prefix[i]` is the frequency after the first `i` changes. If no frequency repeats within the
+/// first pass, a later repeat needs two prefix sums that differ by a multiple of the drift. For
+/// each prefix sum, the earliest such repeat comes from the closest prefix sum in the direction
+/// of the drift with the same remainder modulo the drift.
+fn first_repeat(changes: &[i32]) -> Result<Repeat, Error> {
+    let length = changes.len();
+    if length == 0 {
+        bail!("No frequency changes");
+    }
+    let mut prefix = Vec::with_capacity(length);
+    let mut drift = 0;
+    for &change in changes {
+        prefix.push(drift);
+        drift += i64::from(change);
+    }
+
+    let mut seen = HashSet::new();
+    for (i, &frequency) in prefix.iter().enumerate() {
+        if !seen.insert(frequency) {
+            return Ok(Repeat::new(frequency, i, length));
+        }
+    }
+    if drift == 0 {
+        return Ok(Repeat::new(0, length, length));
+    }
+
+    // Orient the prefix sums so that the frequency increases by `step` each pass.
+    let sign = drift.signum();
+    let step = drift.abs();
+    let mut classes: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (i, &frequency) in prefix.iter().enumerate() {
+        let oriented = frequency * sign;
+        classes
+            .entry(oriented.rem_euclid(step))
+            .or_insert_with(Vec::new)
+            .push((oriented, i));
+    }
+
+    let mut best: Option<(usize, i64)> = None;
+    for class in classes.values_mut() {
+        class.sort();
+        for pair in class.windows(2) {
+            let ((lower, i), (higher, _)) = (pair[0], pair[1]);
+            let passes = ((higher - lower) / step) as usize;
+            let time = passes * length + i;
+            if best.is_none_or(|(best_time, _)| time < best_time) {
+                best = Some((time, higher * sign));
+            }
+        }
+    }
+
+    best.map(|(time, frequency)| Repeat::new(frequency, time, length))
+        .ok_or_else(|| {
+            format_err!(
+                "The frequency drifts by {} each pass and never repeats",
+                drift
+            )
+        })
+}
+
+fn solve1(input: &mut Input, _options: &Opt) -> Result<Output, Error> {
+    Ok(format!("{}", input.iter().sum::<i32>()))
+}
+
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let repeat = first_repeat(input)?;
+    if options.report {
+        Ok(format!(
+            "{}\nReached again after {} changes, at line {} in pass {}",
+            repeat.frequency, repeat.changes, repeat.line, repeat.pass
+        ))
+    } else {
+        Ok(format!("{}", repeat.frequency))
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "day1")]
+struct Opt {
+    /// Part to solve, either 1 or 2
+    #[structopt(short = "-p", long = "--part", default_value = "1")]
+    part: u8,
+    /// Report when the first repeated frequency is reached again
+    #[structopt(short = "-r", long = "--report")]
+    report: bool,
+}
+
+fn run() -> Result<(), Error> {
+    let mut input = read_input()?;
+
+    let options: Opt = Opt::from_args();
+
+    let output = if options.part == 1 {
+        solve1(&mut input, &options)?
+    } else {
+        solve2(&mut input, &options)?
+    };
+
+    println!("{}", output);
+    Ok(())
+}
+
+fn main() {
+    match run() {
+        Ok(()) => process::exit(0),
+        Err(error) => {
+            eprintln!("Error while solving problem: {}", error);
+            for cause in error.iter_causes() {
+                eprintln!("{}", cause)
+            }
+            process::exit(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Apply the changes until a frequency repeats, giving up after `limit` changes.
+    fn simulate(changes: &[i32], limit: usize) -> Option<(i64, usize)> {
+        let mut frequency = 0;
+        let mut seen = HashSet::new();
+        seen.insert(frequency);
+        for (time, &change) in changes.iter().cycle().take(limit).enumerate() {
+            frequency += i64::from(change);
+            if !seen.insert(frequency) {
+                return Some((frequency, time + 1));
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_first_repeat_matches_simulation() {
+        let mut state = 12345u64;
+        let mut random = move |range: i32| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            (state >> 33) as i32 % range
+        };
+        for _ in 0..500 {
+            let length = 1 + random(8) as usize;
+            let changes = (0..length).map(|_| random(21) - 10).collect::<Vec<_>>();
+            let expected = simulate(&changes, 10_000);
+            let actual = first_repeat(&changes)
+                .ok()
+                .map(|repeat| (repeat.frequency, repeat.changes));
+            assert_eq!(actual, expected, "{:?}", changes);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+#[test]
+fn sample1_1() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "1
+2
+3",
+        )
+        .stdout()
+        .is("6")
+        .unwrap();
+}
+
+#[test]
+fn puzzle1() {
+    assert_cli::Assert::main_binary()
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("427")
+        .unwrap();
+}
+
+#[test]
+fn sample2_1() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "+1
+-1",
+        )
+        .stdout()
+        .is("0")
+        .unwrap();
+}
+
+#[test]
+fn sample2_2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "+3
++3
++4
+-2
+-4
+",
+        )
+        .stdout()
+        .is("10")
+        .unwrap();
+}
+
+#[test]
+fn sample2_3() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "-6
++3
++8
++5
+-6",
+        )
+        .stdout()
+        .is("5")
+        .unwrap();
+}
+
+#[test]
+fn sample2_4() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "+7
++7
+-2
+-7
+-4",
+        )
+        .stdout()
+        .is("14")
+        .unwrap();
+}
+
+#[test]
+fn puzzle2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("341")
+        .unwrap();
+}
+
+#[test]
+fn sample2_report() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--report"])
+        .stdin(
+            "+7
++7
+-2
+-7
+-4",
+        )
+        .stdout()
+        .is("14
+Reached again after 13 changes, at line 3 in pass 3")
+        .unwrap();
+}
+
+#[test]
+fn never_repeats2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "+1
++1",
+        )
+        .fails()
+        .and()
+        .stderr()
+        .contains("never repeats")
+        .unwrap();
+}
@@ -8,6 +8,7 @@
 
 use std::char;
 use std::cmp::{max, min};
+use std::convert::TryFrom;
 use std::fmt;
 use std::io::BufRead;
 use std::iter::*;
@@ -29,6 +30,7 @@ use tap::{TapOps, TapOptionOps, TapResultOps};
 
 use aoc2018::input::*;
 use aoc2018::matrix::*;
+use aoc2018::position::*;
 use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -49,9 +51,27 @@ impl Star {
         Star { x, y, dx, dy }
     }
 
-    fn step(&mut self) {
-        self.x += self.dx;
-        self.y += self.dy;
+    /// The coordinates after `second` seconds, computed wide enough not to overflow for any
+    /// second the search looks at, and saturating beyond that.
+    fn position_at(&self, second: i64) -> (i64, i64) {
+        (
+            i64::from(self.x).saturating_add(i64::from(self.dx).saturating_mul(second)),
+            i64::from(self.y).saturating_add(i64::from(self.dy).saturating_mul(second)),
+        )
+    }
+
+    /// The star after `second` seconds, or an error if it has left the coordinates of the sky.
+    fn at(&self, second: i64) -> Result<Star, Error> {
+        let (x, y) = self.position_at(second);
+        match (i32::try_from(x), i32::try_from(y)) {
+            (Ok(x), Ok(y)) => Ok(Star::new(x, y, self.dx, self.dy)),
+            _ => bail!(
+                "A star is at ({}, {}) at second {}, outside the coordinates of the sky",
+                x,
+                y,
+                second
+            ),
+        }
     }
 }
 
@@ -71,101 +91,84 @@ impl FromStr for Star {
 #[derive(Ord, PartialOrd, PartialEq, Eq, Hash, Clone, Debug)]
 struct Sky {
     stars: Vec<Star>,
-    second: usize,
-    min_x: i32,
-    min_y: i32,
-    max_x: i32,
-    max_y: i32,
+    second: i64,
+    bounds: Rect,
 }
 
-fn bounds(stars: &[Star]) -> (i32, i32, i32, i32) {
-    let mut it = stars.iter();
-    let first = it.next().unwrap();
-    let mut min_x: i32 = first.x;
-    let mut min_y: i32 = first.y;
-    let mut max_x: i32 = first.x;
-    let mut max_y: i32 = first.y;
-    for star in it {
-        min_x = min(min_x, star.x);
-        min_y = min(min_y, star.y);
-        max_x = max(max_x, star.x);
-        max_y = max(max_y, star.y);
-    }
-
-    (min_x, min_y, max_x, max_y)
+fn bounds(stars: &[Star]) -> Rect {
+    Rect::from_points(stars.iter().map(|star| (star.x, star.y))).expect("The sky has stars")
 }
 
 impl Sky {
-    fn new(stars: &[Star]) -> Sky {
-        let (min_x, min_y, max_x, max_y) = bounds(stars);
-        Sky {
-            stars: Vec::from(stars),
-            second: 0,
-            min_x,
-            min_y,
-            max_x,
-            max_y,
-        }
+    /// The sky after `second` seconds, computed directly from the initial stars.
+    fn at(stars: &[Star], second: i64) -> Result<Sky, Error> {
+        let stars = stars
+            .iter()
+            .map(|star| star.at(second))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let bounds = bounds(&stars);
+        Ok(Sky {
+            stars,
+            second,
+            bounds,
+        })
     }
 
     fn height(&self) -> usize {
-        (self.max_y - self.min_y) as usize + 1
+        self.bounds.height()
     }
 
     fn width(&self) -> usize {
-        (self.max_x - self.min_x) as usize + 1
-    }
-
-    fn step(&mut self) {
-        self.stars.iter_mut().for_each(Star::step);
-        let (min_x, min_y, max_x, max_y) = bounds(&self.stars);
-        self.min_x = min_x;
-        self.max_x = max_x;
-        self.min_y = min_y;
-        self.max_y = max_y;
-        self.second += 1;
+        self.bounds.width()
     }
 }
 
-struct SkyIter {
-    sky: Sky,
-}
-
-impl Iterator for SkyIter {
-    type Item = Sky;
-
-    fn next(&mut self) -> Option<Sky> {
-        self.sky.step();
-        Some(self.sky.clone())
-    }
+/// How to measure the spread of the stars, the message is where it is smallest.
+#[derive(EnumString, Debug, Copy, Clone, PartialEq, Eq)]
+enum Criterion {
+    #[strum(serialize = "area")]
+    Area,
+    #[strum(serialize = "height")]
+    Height,
+    #[strum(serialize = "perimeter")]
+    Perimeter,
 }
 
-impl IntoIterator for Sky {
-    type Item = Sky;
-    type IntoIter = SkyIter;
-
-    fn into_iter(self) -> SkyIter {
-        SkyIter { sky: self }
+impl Criterion {
+    fn measure(self, stars: &[Star], second: i64) -> i64 {
+        let mut positions = stars.iter().map(|star| star.position_at(second));
+        let first = positions.next().expect("The sky has stars");
+        let (min_x, min_y, max_x, max_y) = positions.fold(
+            (first.0, first.1, first.0, first.1),
+            |(min_x, min_y, max_x, max_y), (x, y)| {
+                (min(min_x, x), min(min_y, y), max(max_x, x), max(max_y, y))
+            },
+        );
+        let width = max_x.saturating_sub(min_x).saturating_add(1);
+        let height = max_y.saturating_sub(min_y).saturating_add(1);
+        match self {
+            Criterion::Area => width.saturating_mul(height),
+            Criterion::Height => height,
+            Criterion::Perimeter => width.saturating_add(height).saturating_mul(2),
+        }
     }
 }
 
-impl<'a> IntoIterator for &'a Sky {
-    type Item = Sky;
-    type IntoIter = SkyIter;
-
-    fn into_iter(self) -> SkyIter {
-        SkyIter { sky: self.clone() }
+impl Sky {
+    fn matrix(&self) -> Matrix {
+        let mut matrix = Matrix::new(self.width(), self.height());
+        for star in self.stars.iter() {
+            let x = (star.x - self.bounds.min.x) as usize;
+            let y = (star.y - self.bounds.min.y) as usize;
+            matrix.set(x, y, true);
+        }
+        matrix
     }
 }
 
 impl Display for Sky {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        let mut matrix = Matrix::new(self.width(), self.height());
-        for star in self.stars.iter() {
-            let x = (star.x - self.min_x) as usize;
-            let y = (star.y - self.min_y) as usize;
-            matrix[(x, y)] = true;
-        }
+        let matrix = self.matrix();
         for y in 0..matrix.height {
             for x in 0..matrix.width {
                 write!(f, "{}", if matrix[(x, y)] { '#' } else { '·' })?;
@@ -189,24 +192,62 @@ fn read_input() -> Result<Input, Error> {
     Ok(result)
 }
 
-fn find_message(input: &&mut Vec<Star>) -> Result<Sky, Error> {
-    let sky = Sky::new(&input);
-    let candidates = sky
-        .into_iter()
-        .skip_while(|sky| sky.height() > 100)
-        .take_while(|sky| sky.height() <= 100);
-    candidates
-        .min_by_key(|s| s.height())
-        .ok_or_else(|| err_msg("No message found"))
+/// Find the second where the stars are least spread out according to the criterion.
+///
+/// The spread shrinks while the stars converge and grows after, so the second
+/// is bracketed by doubling and then found by ternary search.
+///
+/// When the two probes of the ternary search measure the same, the later part of the bracket is
+/// dropped, and the earliest second in the final bracket is chosen. Height, width and perimeter
+/// are convex over time, so they can only be flat around their minimum, and equal probes always
+/// have a minimum between them. This finds the earliest second with the smallest spread. The area
+/// is not convex in general, but is unimodal for stars that converge on a message.
+fn find_message(input: &Input, criterion: Criterion, max_height: usize) -> Result<Sky, Error> {
+    const MAX_SECONDS: i64 = 1 << 40;
+    if input.is_empty() {
+        bail!("No stars in the sky");
+    }
+    let measure = |second: i64| criterion.measure(input, second);
+
+    let mut high = 1;
+    while high < MAX_SECONDS && measure(2 * high) < measure(high) {
+        high *= 2;
+    }
+    let (mut low, mut high) = (0, 2 * high);
+    while high - low > 2 {
+        let first = low + (high - low) / 3;
+        let second = high - (high - low) / 3;
+        if measure(first) <= measure(second) {
+            high = second;
+        } else {
+            low = first;
+        }
+    }
+    let second = (low..=high).min_by_key(|&second| measure(second)).unwrap();
+
+    let height = Criterion::Height.measure(input, second);
+    if height > max_height as i64 {
+        bail!(
+            "No message found, the stars are at least {} high at second {}",
+            height,
+            second
+        );
+    }
+    Sky::at(input, second)
+}
+
+fn solve1(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let message = find_message(input, options.criterion, options.max_height)?;
+    ocr(&message.matrix())
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let message = find_message(&input)?;
-    Ok(message.to_string().to_owned())
+fn render(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let message = find_message(input, options.criterion, options.max_height)?;
+    Ok(message.to_string())
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
-    let message = find_message(&input)?;
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let message = find_message(input, options.criterion, options.max_height)?;
     Ok(format!("{}", message.second))
 }
 
@@ -216,6 +257,15 @@ struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Print the message as stars instead of reading its text
+    #[structopt(short = "-r", long = "--render")]
+    render: bool,
+    /// How to measure the spread of the stars, one of area, height or perimeter
+    #[structopt(long = "--criterion", default_value = "area")]
+    criterion: Criterion,
+    /// The maximum height of a message
+    #[structopt(long = "--max-height", default_value = "100")]
+    max_height: usize,
 }
 
 fn run() -> Result<(), Error> {
@@ -223,10 +273,12 @@ fn run() -> Result<(), Error> {
 
     let options: Opt = Opt::from_args();
 
-    let output = if options.part == 1 {
-        solve1(&mut input)?
+    let output = if options.render {
+        render(&mut input, &options)?
+    } else if options.part == 1 {
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
@@ -1,8 +1,7 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
+        .with_args(&["--render"])
         .stdin(
             "position=< 9,  1> velocity=< 0,  2>
 position=< 7,  0> velocity=<-1,  0>
@@ -51,6 +50,16 @@ position=<-3,  6> velocity=< 2, -1>",
 #[test]
 fn puzzle1() {
     assert_cli::Assert::main_binary()
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("GGLZLHCE")
+        .unwrap();
+}
+
+#[test]
+fn puzzle1_render() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["--render"])
         .stdin(include_str!("../data/puzzle1.in"))
         .stdout()
         .is("·####····####···#·······######··#·······#····#···####···######
@@ -117,3 +126,55 @@ fn puzzle2() {
         .is("10144")
         .unwrap();
 }
+
+#[test]
+fn huge_spread2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "position=<-3000000, 0> velocity=< 3, 0>
+position=< 3000000, 1> velocity=<-3, 0>
+position=<       0, 2> velocity=< 0, 0>",
+        )
+        .stdout()
+        .is("1000000")
+        .unwrap();
+}
+
+#[test]
+fn puzzle2_height() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--criterion", "height"])
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("10144")
+        .unwrap();
+}
+
+#[test]
+fn far_convergence2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "position=<-2000000000, 0> velocity=< 2, 0>
+position=< 2000000000, 1> velocity=< 0, 0>",
+        )
+        .stdout()
+        .is("2000000000")
+        .unwrap();
+}
+
+#[test]
+fn outside_sky2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "position=<-2000000000, 0> velocity=< -2, 0>
+position=< 2000000000, 1> velocity=< -4, 0>",
+        )
+        .fails()
+        .and()
+        .stderr()
+        .contains("outside the coordinates of the sky")
+        .unwrap();
+}
@@ -7,7 +7,7 @@
 )]
 
 use std::char;
-use std::cmp::{max, min};
+use std::cmp::{max, min, Reverse};
 use std::fmt;
 use std::io::BufRead;
 use std::iter::*;
@@ -18,6 +18,7 @@ use std::{io, process};
 
 use failure::bail;
 use failure::err_msg;
+use failure::format_err;
 use failure::Error;
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
@@ -29,12 +30,23 @@ use tap::{TapOps, TapOptionOps, TapResultOps};
 
 use aoc2018::input::*;
 use aoc2018::matrix::*;
+use aoc2018::position::*;
+use aoc2018::summed_area::SummedAreaTable;
 use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
 type Input = usize;
-type Output = (usize, usize, usize);
+type Output = Square;
+
+/// A square of fuel cells, with its top left corner, size and total power.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Square {
+    x: usize,
+    y: usize,
+    size: usize,
+    power: i64,
+}
 
 fn read_input() -> Result<Input, Error> {
     let stdin = io::stdin();
@@ -49,103 +61,159 @@ fn read_input() -> Result<Input, Error> {
     bail!("No input found")
 }
 
-fn grid(serial: usize) -> Vec<Vec<i64>> {
-    let mut grid = vec![vec![0; 300]; 300];
-
-    for x in 0..300 {
-        for y in 0..300 {
-            grid[x][y] = power_level(serial, x + 1, y + 1);
-        }
-    }
+fn power_table(
+    formula: &Formula,
+    serial: usize,
+    width: usize,
+    height: usize,
+) -> SummedAreaTable<i64> {
+    let bounds = Rect::new((1, 1).into(), (width, height).into());
+    SummedAreaTable::from_fn(bounds, |cell| {
+        formula.power_level(serial, cell.x as usize, cell.y as usize)
+    })
+}
 
-    grid
+/// The square with the largest total power among `sizes`, searching groups of sizes in parallel.
+///
+/// Ties are broken in favour of the smallest size, then the first corner in reading order.
+fn best_square(table: &SummedAreaTable<i64>, sizes: RangeInclusive<usize>) -> Option<Square> {
+    const GROUP: usize = 4;
+    sizes
+        .collect::<Vec<_>>()
+        .par_chunks(GROUP)
+        .filter_map(|group| {
+            table
+                .best_square_of_sizes(group.iter().cloned())
+                .map(|(corner, size, power)| Square {
+                    x: corner.x as usize,
+                    y: corner.y as usize,
+                    size,
+                    power,
+                })
+        })
+        .reduce_with(|a, b| {
+            if (b.power, Reverse(b.size)) > (a.power, Reverse(a.size)) {
+                b
+            } else {
+                a
+            }
+        })
 }
 
-fn power_level(serial: usize, x: usize, y: usize) -> i64 {
-    let rack_id = x + 10;
-    let power = y * rack_id;
-    let power_start = power + serial;
-    let power_indication = (power_start) * rack_id;
-    let power_base = (power_indication / 100) % 10;
-    power_base as i64 - 5
+fn solve(serial: usize, options: &Opt, min_size: usize, max_size: usize) -> Result<Output, Error> {
+    let min_size = options.min_size.unwrap_or(min_size).max(1);
+    let max_size = options
+        .max_size
+        .unwrap_or(max_size)
+        .min(options.width)
+        .min(options.height);
+    let formula = Formula {
+        rack_offset: options.rack_offset,
+        digit: options.digit,
+        offset: options.power_offset,
+    };
+    let table = power_table(&formula, serial, options.width, options.height);
+    best_square(&table, min_size..=max_size).ok_or_else(|| {
+        format_err!(
+            "No square of size {} to {} fits in a {}x{} grid",
+            min_size,
+            max_size,
+            options.width,
+            options.height
+        )
+    })
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let grid = grid(*input);
+/// How the power level of a fuel cell is calculated from its coordinates and the grid serial.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Formula {
+    /// Added to the X coordinate to get the rack ID
+    rack_offset: usize,
+    /// The decimal digit of the power to keep, counting from 0 for the ones
+    digit: u32,
+    /// Subtracted from the kept digit to get the power level
+    offset: i64,
+}
 
-    let mut res_x = 0;
-    let mut res_y = 0;
-    let mut res_power = -10000;
+impl Formula {
+    fn power_level(&self, serial: usize, x: usize, y: usize) -> i64 {
+        let rack_id = x + self.rack_offset;
+        let power = (y * rack_id + serial) * rack_id;
+        let digit = 10usize
+            .checked_pow(self.digit)
+            .map_or(0, |place| (power / place) % 10);
+        digit as i64 - self.offset
+    }
+}
 
-    for x in 0..(300 - 2) {
-        for y in 0..(300 - 2) {
-            let mut power = 0;
-            for dx in 0..3 {
-                for dy in 0..3 {
-                    power += grid[x + dx][y + dy];
-                }
-            }
-            if power > res_power {
-                res_power = power;
-                res_x = x;
-                res_y = y;
-            }
+impl Default for Formula {
+    fn default() -> Self {
+        Formula {
+            rack_offset: 10,
+            digit: 2,
+            offset: 5,
         }
     }
-
-    Ok((res_x + 1, res_y + 1, 3))
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
-    let grid = grid(*input);
-
-    let mut res_x = 0;
-    let mut res_y = 0;
-    let mut res_power = -10000;
-    let mut res_d = 0;
+fn solve1(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    solve(*input, options, 3, 3)
+}
 
-    // Cumulative sums for box from bottom left corner up to (x,y)
-    let mut sums = vec![vec![0; 300]; 300];
-    sums[299][299] = grid[299][299];
-    for i in (0..299).rev() {
-        sums[299][i] = grid[299][i] + sums[299][i + 1];
-        sums[i][299] = grid[i][299] + sums[i + 1][299];
-    }
-    let mut y_sum = vec![0; 300];
-    for x in (0..299).rev() {
-        y_sum[299] = grid[x][299];
-        for y in (0..299).rev() {
-            y_sum[y] = y_sum[y + 1] + grid[x][y];
-        }
-        for y in (0..299).rev() {
-            sums[x][y] = y_sum[y] + sums[x + 1][y];
-        }
-    }
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    solve(*input, options, 1, usize::MAX)
+}
 
-    for x in 0..300 {
-        for y in 0..300 {
-            let max_d = 300 - max(x, y);
-            for d in 1..max_d {
-                let power = sums[x][y] - sums[x + d][y] - sums[x][y + d] + sums[x + d][y + d];
-                if power >= res_power {
-                    res_power = power;
-                    res_x = x;
-                    res_y = y;
-                    res_d = d;
-                }
-            }
-        }
+/// Parse a grid dimension, which must be at least 1.
+fn parse_dimension(s: &str) -> Result<usize, Error> {
+    let dimension = s.parse()?;
+    if dimension < 1 {
+        bail!(
+            "The grid must be at least 1 cell wide and high, not {}",
+            dimension
+        );
     }
-
-    Ok((res_x + 1, res_y + 1, res_d))
+    Ok(dimension)
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "day6")]
+#[structopt(name = "day11")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Width of the grid of fuel cells
+    #[structopt(
+        long = "--width",
+        default_value = "300",
+        parse(try_from_str = "parse_dimension")
+    )]
+    width: usize,
+    /// Height of the grid of fuel cells
+    #[structopt(
+        long = "--height",
+        default_value = "300",
+        parse(try_from_str = "parse_dimension")
+    )]
+    height: usize,
+    /// Smallest square size to search, default 3 for part 1 and 1 for part 2
+    #[structopt(long = "--min-size")]
+    min_size: Option<usize>,
+    /// Largest square size to search, default 3 for part 1 and the grid size for part 2
+    #[structopt(long = "--max-size")]
+    max_size: Option<usize>,
+    /// Added to the X coordinate of a fuel cell to get its rack ID
+    #[structopt(long = "--rack-offset", default_value = "10")]
+    rack_offset: usize,
+    /// Decimal digit of the power to keep, counting from 0 for the ones
+    #[structopt(long = "--digit", default_value = "2")]
+    digit: u32,
+    /// Subtracted from the kept digit to get the power level of a fuel cell
+    #[structopt(long = "--power-offset", default_value = "5")]
+    power_offset: i64,
+    /// Print the total power of the best square after it
+    #[structopt(long = "--power")]
+    power: bool,
 }
 
 fn run() -> Result<(), Error> {
@@ -154,12 +222,17 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
-    println!("{:?}", output);
+    let square = (output.x, output.y, output.size);
+    if options.power {
+        println!("{:?} {}", square, output.power);
+    } else {
+        println!("{:?}", square);
+    }
     Ok(())
 }
 
@@ -182,9 +255,31 @@ mod test {
 
     #[test]
     fn test_power_level() {
-        assert_eq!(power_level(8, 3, 5), 4);
-        assert_eq!(power_level(57, 122, 79), -5);
-        assert_eq!(power_level(39, 217, 196), 0);
-        assert_eq!(power_level(71, 101, 153), 4);
+        let formula = Formula::default();
+        assert_eq!(formula.power_level(8, 3, 5), 4);
+        assert_eq!(formula.power_level(57, 122, 79), -5);
+        assert_eq!(formula.power_level(39, 217, 196), 0);
+        assert_eq!(formula.power_level(71, 101, 153), 4);
+    }
+
+    #[test]
+    fn test_custom_formula() {
+        // Rack ID 5, power (7 * 5 + 8) * 5 = 215.
+        let formula = Formula {
+            rack_offset: 2,
+            digit: 0,
+            offset: 1,
+        };
+        assert_eq!(formula.power_level(8, 3, 7), 4);
+        let formula = Formula {
+            digit: 1,
+            ..formula
+        };
+        assert_eq!(formula.power_level(8, 3, 7), 0);
+        let formula = Formula {
+            digit: 30,
+            ..formula
+        };
+        assert_eq!(formula.power_level(8, 3, 7), -1);
     }
 }
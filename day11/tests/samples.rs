@@ -1,5 +1,3 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
@@ -56,3 +54,89 @@ fn puzzle2() {
         .is("(142, 265, 7)")
         .unwrap();
 }
+
+#[test]
+fn sample1_power() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["--power"])
+        .stdin("18")
+        .stdout()
+        .is("(33, 45, 3) 29")
+        .unwrap();
+}
+
+#[test]
+fn sample2_power() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--power"])
+        .stdin("18")
+        .stdout()
+        .is("(90, 269, 16) 113")
+        .unwrap();
+}
+
+#[test]
+fn sample2_small_grid() {
+    assert_cli::Assert::main_binary()
+        .with_args(&[
+            "-p",
+            "2",
+            "--width",
+            "50",
+            "--height",
+            "40",
+            "--max-size",
+            "10",
+            "--power",
+        ])
+        .stdin("18")
+        .stdout()
+        .is("(39, 34, 7) 37")
+        .unwrap();
+}
+
+#[test]
+fn too_small_grid() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["--width", "2"])
+        .stdin("18")
+        .fails()
+        .and()
+        .stderr()
+        .contains("No square of size 3 to 2 fits in a 2x300 grid")
+        .unwrap();
+}
+
+#[test]
+fn empty_grid() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--height", "0"])
+        .stdin("18")
+        .fails()
+        .and()
+        .stderr()
+        .contains("The grid must be at least 1 cell wide and high, not 0")
+        .unwrap();
+}
+
+#[test]
+fn custom_formula() {
+    assert_cli::Assert::main_binary()
+        .with_args(&[
+            "--width",
+            "4",
+            "--height",
+            "4",
+            "--rack-offset",
+            "1",
+            "--digit",
+            "0",
+            "--power-offset",
+            "0",
+            "--power",
+        ])
+        .stdin("18")
+        .stdout()
+        .is("(1, 1, 3) 30")
+        .unwrap();
+}
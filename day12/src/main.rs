@@ -27,8 +27,11 @@ use structopt::StructOpt;
 use strum_macros::EnumString;
 use tap::{TapOps, TapOptionOps, TapResultOps};
 
+use aoc2018::export::Colour;
 use aoc2018::input::*;
 use aoc2018::matrix::*;
+use aoc2018::position::*;
+use aoc2018::viz::{Animation, Cell};
 use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -108,6 +111,17 @@ impl State {
         self.data = next;
     }
 
+    /// Whether the pot with number `pot` has a plant.
+    fn has_plant(&self, pot: i64) -> bool {
+        let index = pot - self.offset;
+        index >= 0 && self.data.get(index as usize).cloned().unwrap_or(false)
+    }
+
+    /// The number of the last pot that is kept track of.
+    fn last_pot(&self) -> i64 {
+        self.offset + self.data.len() as i64 - 1
+    }
+
     fn value(&self) -> i64 {
         self.data
             .iter()
@@ -131,7 +145,7 @@ impl FromStr for State {
 impl Display for State {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         for i in 0..self.data.len() {
-            if i == self.offset.abs() as usize {
+            if i == self.offset.unsigned_abs() as usize {
                 write!(f, "|")?;
             }
             if self.data[i] {
@@ -187,6 +201,7 @@ impl Rules {
     }
 }
 
+#[allow(clippy::lines_filter_map_ok)]
 fn read_input() -> Result<Input, Error> {
     let stdin = io::stdin();
     let mut lines = stdin.lock().lines();
@@ -207,25 +222,74 @@ fn read_input() -> Result<Input, Error> {
     Ok((state, rules))
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let (state, rules) = input;
+const PLANT_COLOUR: Colour = Colour::new(64, 192, 64);
+const POT_COLOUR: Colour = Colour::new(96, 64, 32);
+
+/// Draw a frame of the animation, with one row for each generation so far, following the latest.
+fn animate(animation: &mut Option<Animation<io::Stdout>>, history: &[State]) -> Result<(), Error> {
+    let animation = match animation {
+        Some(animation) => animation,
+        None => return Ok(()),
+    };
+    let latest = match history.last() {
+        Some(latest) => latest,
+        None => return Ok(()),
+    };
+    let generation = history.len() - 1;
+    let first = history.iter().map(|state| state.offset).min().unwrap();
+    let last = history.iter().map(State::last_pot).max().unwrap();
+    let bounds = Rect::new(
+        Position::new(first as Scalar, 0),
+        Position::new(last as Scalar, generation as Scalar),
+    );
+    animation.follow(Position::new(0, generation as Scalar));
+    animation.draw(
+        bounds,
+        |position| {
+            if history[position.y as usize].has_plant(i64::from(position.x)) {
+                Cell::new('#', PLANT_COLOUR)
+            } else {
+                Cell::new('.', POT_COLOUR)
+            }
+        },
+        &format!("generation {}, sum {}", generation, latest.value()),
+    )
+}
 
-    for _ in 0..20 {
+/// Step the state `generations` times, animating each generation if asked to.
+fn grow(state: &mut State, rules: &Rules, generations: i64, options: &Opt) -> Result<(), Error> {
+    let mut animation = if options.animate {
+        Some(Animation::stdout().with_fps(options.fps))
+    } else {
+        None
+    };
+    let mut history = vec![state.clone()];
+    animate(&mut animation, &history)?;
+    for _ in 0..generations {
         state.step(rules);
+        if animation.is_some() {
+            history.push(state.clone());
+            animate(&mut animation, &history)?;
+        }
     }
+    Ok(())
+}
+
+fn solve1(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let (state, rules) = input;
+
+    grow(state, rules, 20, options)?;
 
     Ok(state.value())
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
     let (state, rules) = input;
 
     const START: i64 = 1000;
 
     let mut current = state.clone();
-    for _ in 0..START {
-        current.step(rules);
-    }
+    grow(&mut current, rules, START, options)?;
     let v1 = current.value();
     current.step(rules);
     let v2 = current.value();
@@ -235,11 +299,17 @@ fn solve2(input: &mut Input) -> Result<Output, Error> {
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "day6")]
+#[structopt(name = "day12")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Animate the generations of plants on the terminal
+    #[structopt(short = "-a", long = "--animate")]
+    animate: bool,
+    /// Frames per second when animating, 0 for no limit
+    #[structopt(long = "--fps", default_value = "10")]
+    fps: u32,
 }
 
 fn run() -> Result<(), Error> {
@@ -248,9 +318,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{:?}", output);
@@ -27,11 +27,13 @@ use structopt::StructOpt;
 use strum_macros::EnumString;
 use tap::{TapOps, TapOptionOps, TapResultOps};
 
+use aoc2018::export::Colour;
 use aoc2018::input::*;
 use aoc2018::matrix::*;
 use aoc2018::position::Step;
 use aoc2018::position::*;
 use aoc2018::sparse_grid::*;
+use aoc2018::viz::{Animation, Cell};
 use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -76,13 +78,25 @@ impl Track {
             _ => None,
         }
     }
+
+    fn glyph(self) -> char {
+        match self {
+            Horizontal { .. } => '-',
+            Vertical { .. } => '|',
+            DLCorner { .. } => '\\',
+            DRCorner { .. } => '/',
+            ULCorner { .. } => '/',
+            URCorner { .. } => '\\',
+            Crossing { .. } => '+',
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 struct Cart {
     position: Position,
     direction: Direction,
-    next_turn: Option<Turn>,
+    next_turn: Turn,
 }
 
 impl Cart {
@@ -90,7 +104,7 @@ impl Cart {
         Cart {
             position,
             direction,
-            next_turn: Some(Turn::Left),
+            next_turn: Turn::Left,
         }
     }
 
@@ -111,11 +125,15 @@ impl Cart {
             (ULCorner { .. }, Down) => (Left, self.next_turn),
             (URCorner { .. }, Left) => (Up, self.next_turn),
             (URCorner { .. }, Down) => (Right, self.next_turn),
-            (Crossing { .. }, _) => match self.next_turn {
-                Some(Turn::Left) => (self.direction.turn(Turn::Left), None),
-                None => (self.direction, Some(Turn::Right)),
-                Some(Turn::Right) => (self.direction.turn(Turn::Right), Some(Turn::Left)),
-            },
+            (Crossing { .. }, _) => {
+                let next_turn = match self.next_turn {
+                    Turn::Left => Turn::Straight,
+                    Turn::Straight => Turn::Right,
+                    Turn::Right => Turn::Left,
+                    Turn::Back => unreachable!("Carts never turn back at crossings"),
+                };
+                (self.direction.turn(self.next_turn), next_turn)
+            }
             _ => (self.direction, self.next_turn),
         };
 
@@ -209,33 +227,35 @@ fn read_input() -> Result<Input, Error> {
     Ok((grid, carts))
 }
 
+impl Cart {
+    fn glyph(self) -> char {
+        match self.direction {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Right => '>',
+            Direction::Left => '<',
+        }
+    }
+}
+
 fn print(grid: &Grid<Track>, carts: &[Cart]) {
     let mut cart_map = HashMap::new();
     carts.iter().for_each(|c| {
         cart_map.insert(c.position, c);
     });
 
-    for y in grid.min_y..=grid.max_y {
-        for x in grid.min_x..=grid.max_x {
+    let bounds = match grid.bounds {
+        Some(bounds) => bounds,
+        None => return,
+    };
+    for y in bounds.min.y..=bounds.max.y {
+        for x in bounds.min.x..=bounds.max.x {
             print!(
                 "{}",
                 if let Some(cart) = cart_map.get(&(x, y).into()) {
-                    match cart.direction {
-                        Direction::Up => '^',
-                        Direction::Down => 'v',
-                        Direction::Right => '>',
-                        Direction::Left => '<',
-                    }
+                    cart.glyph()
                 } else if let Some(&track) = grid.get((x, y).into()) {
-                    match track {
-                        Track::Horizontal { .. } => '-',
-                        Track::Vertical { .. } => '|',
-                        Track::DLCorner { .. } => '\\',
-                        Track::DRCorner { .. } => '/',
-                        Track::ULCorner { .. } => '/',
-                        Track::URCorner { .. } => '\\',
-                        Track::Crossing { .. } => '+',
-                    }
+                    track.glyph()
                 } else {
                     ' '
                 }
@@ -246,17 +266,72 @@ fn print(grid: &Grid<Track>, carts: &[Cart]) {
     println!("    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~    ");
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
+const TRACK_COLOUR: Colour = Colour::new(128, 128, 128);
+const CART_COLOUR: Colour = Colour::new(255, 64, 64);
+
+/// Draw a frame of the animation, following the first cart in reading order.
+fn animate(
+    animation: &mut Option<Animation<io::Stdout>>,
+    grid: &Grid<Track>,
+    carts: &[Cart],
+    tick: usize,
+) -> Result<(), Error> {
+    let animation = match animation {
+        Some(animation) => animation,
+        None => return Ok(()),
+    };
+    let bounds = match grid.bounds {
+        Some(bounds) => bounds,
+        None => return Ok(()),
+    };
+    let cart_map = carts
+        .iter()
+        .map(|&c| (c.position, c))
+        .collect::<HashMap<_, _>>();
+    if let Some(first) = carts
+        .iter()
+        .min_by(|a, b| a.position.cmp_reading_order(&b.position))
+    {
+        animation.follow(first.position);
+    }
+    animation.draw(
+        bounds,
+        |position| {
+            if let Some(cart) = cart_map.get(&position) {
+                Cell::new(cart.glyph(), CART_COLOUR)
+            } else if let Some(&track) = grid.get(position) {
+                Cell::new(track.glyph(), TRACK_COLOUR)
+            } else {
+                ' '.into()
+            }
+        },
+        &format!("tick {}, {} carts", tick, carts.len()),
+    )
+}
+
+fn animation(options: &Opt) -> Option<Animation<io::Stdout>> {
+    if options.animate {
+        Some(Animation::stdout().with_fps(options.fps))
+    } else {
+        None
+    }
+}
+
+fn solve1(input: &mut Input, options: &Opt) -> Result<Output, Error> {
     let (grid, carts) = input;
     let mut carts = carts.clone();
+    let mut animation = animation(options);
 
+    let mut tick = 0;
     loop {
+        animate(&mut animation, grid, &carts, tick)?;
+        tick += 1;
         let mut positions = carts.iter().map(|&c| c.position).collect::<HashSet<_>>();
-        carts.sort();
+        sort_reading_order(&mut carts, |cart| cart.position);
         let mut moved_carts = Vec::new();
         for cart in carts {
             positions.remove(&cart.position);
-            let moved_cart = cart.step(&grid);
+            let moved_cart = cart.step(grid);
             if positions.contains(&moved_cart.position) {
                 return Ok(moved_cart.position);
             }
@@ -267,20 +342,24 @@ fn solve1(input: &mut Input) -> Result<Output, Error> {
     }
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
     let (grid, carts) = input;
     let mut carts = carts.clone();
+    let mut animation = animation(options);
 
+    let mut tick = 0;
     loop {
+        animate(&mut animation, grid, &carts, tick)?;
+        tick += 1;
         let mut positions = carts
             .iter()
             .map(|&c| (c.position, c))
             .collect::<HashMap<_, _>>();
 
-        carts.sort();
+        sort_reading_order(&mut carts, |cart| cart.position);
         for cart in carts {
             if positions.remove(&cart.position).is_some() {
-                let moved_cart = cart.step(&grid);
+                let moved_cart = cart.step(grid);
                 if positions.remove(&moved_cart.position).is_none() {
                     positions.insert(moved_cart.position, moved_cart);
                 }
@@ -299,6 +378,12 @@ struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Animate the carts on the terminal
+    #[structopt(short = "-a", long = "--animate")]
+    animate: bool,
+    /// Frames per second when animating, 0 for no limit
+    #[structopt(long = "--fps", default_value = "10")]
+    fps: u32,
 }
 
 fn run() -> Result<(), Error> {
@@ -307,9 +392,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
@@ -1,5 +1,3 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
@@ -18,6 +18,7 @@ use std::{io, process};
 
 use failure::bail;
 use failure::err_msg;
+use failure::format_err;
 use failure::Error;
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
@@ -48,12 +49,16 @@ fn read_input() -> Result<Input, Error> {
     bail!("No input found")
 }
 
+/// The scoreboard of recipes, which iterates over the scores of all recipes in order, cooking
+/// new ones as needed.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 struct Ledger {
     recipes: Vec<u8>,
     elf1: usize,
     elf2: usize,
     iterations: usize,
+    /// The number of scores returned so far.
+    emitted: usize,
 }
 
 impl Ledger {
@@ -63,6 +68,7 @@ impl Ledger {
             elf1: 0,
             elf2: 1,
             iterations: 0,
+            emitted: 0,
         }
     }
 
@@ -82,6 +88,18 @@ impl Ledger {
     }
 }
 
+impl Iterator for Ledger {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.emitted == self.recipes.len() {
+            self.cook();
+        }
+        self.emitted += 1;
+        Some(self.recipes[self.emitted - 1])
+    }
+}
+
 impl Display for Ledger {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "{}: ", self.iterations)?;
@@ -107,77 +125,103 @@ impl Display for Ledger {
     }
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let input: usize = input.parse()?;
-    let mut ledger = Ledger::new();
-
-    for _ in 0..input + 10 {
-        ledger.cook();
-    }
-
-    let next10 = ledger.recipes[input..input + 10]
-        .iter()
-        .map(|&v| format!("{}", v))
-        .collect::<String>();
-
-    Ok(next10)
+/// Finds the first occurrence of a pattern in a stream of values, one value at a time, using the
+/// Knuth-Morris-Pratt algorithm.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+struct Matcher {
+    pattern: Vec<u8>,
+    /// For each prefix of the pattern, the length of its longest proper prefix that is also a
+    /// suffix of it.
+    fallback: Vec<usize>,
+    /// The length of the prefix of the pattern that the stream currently ends with.
+    matched: usize,
+    /// The number of values seen.
+    seen: usize,
 }
 
-fn contains_at(data: &[u8], pos: usize, pattern: &[u8]) -> bool {
-    for i in 0..pattern.len() {
-        if data[pos + i] != pattern[i] {
-            return false;
+impl Matcher {
+    fn new(pattern: Vec<u8>) -> Matcher {
+        assert!(!pattern.is_empty(), "Empty pattern");
+        let mut fallback = vec![0; pattern.len()];
+        let mut length = 0;
+        for i in 1..pattern.len() {
+            while length > 0 && pattern[i] != pattern[length] {
+                length = fallback[length - 1];
+            }
+            if pattern[i] == pattern[length] {
+                length += 1;
+            }
+            fallback[i] = length;
+        }
+        Matcher {
+            pattern,
+            fallback,
+            matched: 0,
+            seen: 0,
         }
     }
-    true
-}
 
-fn position_of(data: &[u8], pattern: &[u8]) -> Option<usize> {
-    if data.len() < pattern.len() {
-        return None;
-    }
-    for i in 0..=data.len() - pattern.len() {
-        if contains_at(data, i, pattern) {
-            return Some(i);
+    /// Add the next value of the stream, giving the start position of the pattern if the stream
+    /// now ends with it.
+    fn push(&mut self, value: u8) -> Option<usize> {
+        self.seen += 1;
+        if self.matched == self.pattern.len() {
+            self.matched = self.fallback[self.matched - 1];
+        }
+        while self.matched > 0 && self.pattern[self.matched] != value {
+            self.matched = self.fallback[self.matched - 1];
+        }
+        if self.pattern[self.matched] == value {
+            self.matched += 1;
+        }
+        if self.matched == self.pattern.len() {
+            Some(self.seen - self.pattern.len())
+        } else {
+            None
         }
     }
-    None
 }
 
-fn contains(data: &[u8], pattern: &[u8]) -> bool {
-    position_of(data, pattern).is_some()
+fn solve1(input: &mut Input, _options: &Opt) -> Result<Output, Error> {
+    let input: usize = input.parse()?;
+    Ok(Ledger::new()
+        .skip(input)
+        .take(10)
+        .map(|v| format!("{}", v))
+        .collect::<String>())
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
     let pattern = input
         .chars()
-        .map(|c| c.to_string().parse())
+        .map(|c| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| format_err!("Pattern \"{}\" has a non-digit '{}'", input, c))
+        })
         .collect::<Result<Vec<u8>, _>>()?;
 
-    let mut ledger = Ledger::new();
-    while ledger.recipes.len() < pattern.len() + 2 {
-        ledger.cook();
-    }
-
-    while !contains(
-        &ledger.recipes[(ledger.recipes.len() - pattern.len() - 2)..],
-        &pattern,
-    ) {
-        ledger.cook();
+    let mut matcher = Matcher::new(pattern);
+    for (recipes, score) in Ledger::new().enumerate() {
+        if options.limit.is_some_and(|limit| recipes >= limit) {
+            bail!("Pattern {} not found within {} recipes", input, recipes);
+        }
+        if let Some(position) = matcher.push(score) {
+            return Ok(format!("{}", position));
+        }
     }
-
-    Ok(format!(
-        "{}",
-        position_of(&ledger.recipes, &pattern).unwrap()
-    ))
+    unreachable!("The ledger never ends")
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "day6")]
+#[structopt(name = "day14")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Give up if the pattern is not found within this many recipes, for part 2
+    #[structopt(short = "-l", long = "--limit")]
+    limit: Option<usize>,
 }
 
 fn run() -> Result<(), Error> {
@@ -186,9 +230,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
@@ -211,4 +255,33 @@ fn main() {
 #[cfg(test)]
 mod test {
     use super::*;
+
+    fn first_match(pattern: &[u8], stream: &[u8]) -> Option<usize> {
+        let mut matcher = Matcher::new(pattern.to_vec());
+        stream.iter().find_map(|&value| matcher.push(value))
+    }
+
+    #[test]
+    fn test_matcher() {
+        assert_eq!(first_match(&[1, 1, 2], &[1, 1, 1, 2]), Some(1));
+        assert_eq!(first_match(&[1, 2, 1, 3], &[1, 2, 1, 2, 1, 3]), Some(2));
+        assert_eq!(first_match(&[1, 2, 1, 3], &[1, 2, 1, 2, 1, 2]), None);
+        assert_eq!(first_match(&[7], &[3, 7]), Some(1));
+
+        let mut matcher = Matcher::new(vec![1, 1]);
+        let matches = [1, 1, 1, 0, 1, 1]
+            .iter()
+            .filter_map(|&value| matcher.push(value))
+            .collect::<Vec<_>>();
+        assert_eq!(matches, vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn test_ledger_scores() {
+        let scores = Ledger::new().take(20).collect::<Vec<_>>();
+        assert_eq!(
+            scores,
+            vec![3, 7, 1, 0, 1, 0, 1, 2, 4, 5, 1, 5, 8, 9, 1, 6, 7, 7, 9, 2]
+        );
+    }
 }
@@ -1,5 +1,3 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
@@ -94,3 +92,25 @@ fn puzzle2() {
         .is("20195114")
         .unwrap();
 }
+
+#[test]
+fn within_limit2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--limit", "2023"])
+        .stdin("59414")
+        .stdout()
+        .is("2018")
+        .unwrap();
+}
+
+#[test]
+fn beyond_limit2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--limit", "2022"])
+        .stdin("59414")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Pattern 59414 not found within 2022 recipes")
+        .unwrap();
+}
@@ -29,8 +29,10 @@ use tap::{TapOps, TapOptionOps, TapResultOps};
 
 use aoc2018::dense_grid::Grid;
 use aoc2018::dense_grid::*;
+use aoc2018::export::Colour;
 use aoc2018::input::*;
 use aoc2018::matrix::*;
+use aoc2018::viz::{Animation, Cell};
 use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -118,35 +120,43 @@ impl Creature {
     }
 }
 
-#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
 enum Tile {
     Wall,
+    #[default]
     Floor,
     Occupied(Creature),
 }
 
 use crate::Tile::*;
 
-impl Default for Tile {
-    fn default() -> Self {
-        Floor
+impl Tile {
+    fn glyph(self) -> char {
+        match self {
+            Tile::Wall => '#',
+            Tile::Floor => '.',
+            Tile::Occupied(creature) => match creature.race() {
+                Race::Elf => 'E',
+                Race::Goblin => 'G',
+            },
+        }
+    }
+
+    fn colour(self) -> Colour {
+        match self {
+            Tile::Wall => Colour::new(128, 128, 128),
+            Tile::Floor => Colour::new(64, 48, 32),
+            Tile::Occupied(creature) => match creature.race() {
+                Race::Elf => Colour::new(64, 192, 64),
+                Race::Goblin => Colour::new(224, 64, 64),
+            },
+        }
     }
 }
 
 impl Display for Tile {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(
-            f,
-            "{}",
-            match self {
-                Tile::Wall => '#',
-                Tile::Floor => '.',
-                Tile::Occupied(creature) => match creature.race() {
-                    Race::Elf => 'E',
-                    Race::Goblin => 'G',
-                },
-            }
-        )
+        write!(f, "{}", self.glyph())
     }
 }
 
@@ -194,20 +204,73 @@ fn read_input() -> Result<Input, Error> {
     Ok(grid)
 }
 
-fn solve1(_input: &mut Input) -> Result<Output, Error> {
+/// Draw a frame of the animation showing the cave after `round` rounds.
+fn animate(
+    animation: &mut Option<Animation<io::Stdout>>,
+    grid: &Grid<Tile>,
+    round: usize,
+) -> Result<(), Error> {
+    let animation = match animation {
+        Some(animation) => animation,
+        None => return Ok(()),
+    };
+    let count = |race| {
+        grid.values
+            .iter()
+            .filter(|tile| matches!(tile, Occupied(creature) if creature.race() == race))
+            .count()
+    };
+    let status = format!(
+        "round {}, {} elves, {} goblins",
+        round,
+        count(Elf),
+        count(Goblin)
+    );
+    animation.draw_dense_grid(
+        grid,
+        |&tile| Cell::new(tile.glyph(), tile.colour()),
+        &status,
+    )
+}
+
+fn animation(options: &Opt) -> Option<Animation<io::Stdout>> {
+    if options.animate {
+        Some(Animation::stdout().with_fps(options.fps))
+    } else {
+        None
+    }
+}
+
+/// Draw the cave before the combat starts as the first frame, when animating.
+fn show_initial(grid: &Grid<Tile>, options: &Opt) -> Result<(), Error> {
+    if options.animate {
+        animate(&mut animation(options), grid, 0)?;
+    }
+    Ok(())
+}
+
+fn solve1(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    show_initial(input, options)?;
     Ok("1".to_owned())
 }
 
-fn solve2(_input: &mut Input) -> Result<Output, Error> {
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    show_initial(input, options)?;
     Ok("2".to_owned())
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "day6")]
+#[structopt(name = "day15")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Animate the combat on the terminal
+    #[structopt(short = "-a", long = "--animate")]
+    animate: bool,
+    /// Frames per second when animating, 0 for no limit
+    #[structopt(long = "--fps", default_value = "10")]
+    fps: u32,
 }
 
 fn run() -> Result<(), Error> {
@@ -216,9 +279,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
@@ -147,7 +147,7 @@ fn get(mode: ArgumentMode, arg: Word, regs: &Registers) -> Result<Word, Error> {
     match mode {
         ArgumentMode::Immediate => Ok(arg),
         ArgumentMode::Register => {
-            if 0 <= arg && arg < 4 {
+            if (0..4).contains(&arg) {
                 Ok(regs[arg as usize])
             } else {
                 bail!(format!("Argument {} is not a valid register index", arg))
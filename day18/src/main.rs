@@ -30,18 +30,22 @@ use tap::{TapOps, TapOptionOps, TapResultOps};
 
 use aoc2018::dense_grid::Grid;
 use aoc2018::dense_grid::*;
+use aoc2018::export::{Colour, Format, FrameWriter, Image};
 use aoc2018::input::*;
 use aoc2018::matrix::*;
 use aoc2018::position::*;
+use aoc2018::viz::{Animation, Cell};
 use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::path::PathBuf;
 
 type Input = Grid<Tile>;
 type Output = String;
 
-#[derive(EnumString, Enum, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(EnumString, Enum, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
 enum Tile {
+    #[default]
     Empty,
     Open,
     Trees,
@@ -50,24 +54,29 @@ enum Tile {
 
 use crate::Tile::*;
 
-impl Default for Tile {
-    fn default() -> Self {
-        Empty
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.glyph())
     }
 }
 
-impl Display for Tile {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(
-            f,
-            "{}",
-            match *self {
-                Empty => ' ',
-                Open => '.',
-                Trees => '|',
-                Lumberyard => '#',
-            }
-        )
+impl Tile {
+    fn glyph(self) -> char {
+        match self {
+            Empty => ' ',
+            Open => '.',
+            Trees => '|',
+            Lumberyard => '#',
+        }
+    }
+
+    fn colour(self) -> Colour {
+        match self {
+            Empty => Colour::BLACK,
+            Open => Colour::new(196, 164, 108),
+            Trees => Colour::new(34, 139, 34),
+            Lumberyard => Colour::new(112, 66, 20),
+        }
     }
 }
 
@@ -127,13 +136,18 @@ fn checksum(grid: &Grid<Tile>) -> usize {
     frequencies[Trees] * frequencies[Lumberyard]
 }
 
-fn evolve(input: &Grid<Tile>, iterations: usize) -> Grid<Tile> {
+/// Evolve the area, calling `observe` with the initial area and after each iteration.
+fn evolve<F>(input: &Grid<Tile>, iterations: usize, mut observe: F) -> Result<Grid<Tile>, Error>
+where
+    F: FnMut(&Grid<Tile>) -> Result<(), Error>,
+{
     let mut current = input.clone();
+    observe(&current)?;
 
     for _ in 0..iterations {
         let mut next = current.clone();
-        for x in 1..(input.width - 1) {
-            for y in 1..(input.height - 1) {
+        for x in 1..(input.width() - 1) {
+            for y in 1..(input.height() - 1) {
                 let pos: Position = (x, y).into();
                 let tile = current[pos];
                 let neighbours = count(&mut connect8(pos).map(|n| current[n]));
@@ -165,27 +179,86 @@ fn evolve(input: &Grid<Tile>, iterations: usize) -> Grid<Tile> {
         }
 
         current = next;
+        observe(&current)?;
     }
 
-    current
+    Ok(current)
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let result = evolve(input, 10);
+/// An observer for `evolve` that writes the frames and animation requested in the options.
+fn observer(options: &Opt) -> Result<impl FnMut(&Grid<Tile>) -> Result<(), Error>, Error> {
+    let mut frames = match &options.frames {
+        Some(directory) => Some(FrameWriter::new(directory, "day18-", options.format)?),
+        None => None,
+    };
+    let mut animation = if options.animate {
+        Some(Animation::stdout().with_fps(options.fps))
+    } else {
+        None
+    };
+    let scale = options.scale;
+    let mut minute = 0;
+    Ok(move |grid: &Grid<Tile>| {
+        if let Some(frames) = frames.as_mut() {
+            let image = Image::from_dense_grid(grid, |&tile| tile.colour()).with_scale(scale);
+            frames.write(&image)?;
+        }
+        if let Some(animation) = animation.as_mut() {
+            animation.draw_dense_grid(
+                grid,
+                |&tile| Cell::new(tile.glyph(), tile.colour()),
+                &format!("minute {}, checksum {}", minute, checksum(grid)),
+            )?;
+        }
+        minute += 1;
+        Ok(())
+    })
+}
+
+fn solve1(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let result = evolve(input, 10, observer(options)?)?;
     Ok(format!("{}", checksum(&result)))
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
-    let result = evolve(input, 1000);
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let result = evolve(input, 1000, observer(options)?)?;
     Ok(format!("{}", checksum(&result)))
 }
 
+/// Parse the pixels per tile, which must be at least 1.
+fn parse_scale(s: &str) -> Result<usize, Error> {
+    let scale = s.parse()?;
+    if scale < 1 {
+        bail!("The scale must be at least 1 pixel per tile, not {}", scale);
+    }
+    Ok(scale)
+}
+
 #[derive(StructOpt, Debug)]
 #[structopt(name = "day6")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Write an image of each generation into this directory
+    #[structopt(long = "--frames", parse(from_os_str))]
+    frames: Option<PathBuf>,
+    /// Image format of the frames, one of ppm, png or svg
+    #[structopt(long = "--format", default_value = "ppm")]
+    format: Format,
+    /// Pixels per tile in the frames
+    #[structopt(
+        long = "--scale",
+        default_value = "4",
+        parse(try_from_str = "parse_scale")
+    )]
+    scale: usize,
+    /// Animate the area on the terminal
+    #[structopt(short = "-a", long = "--animate")]
+    animate: bool,
+    /// Frames per second when animating, 0 for no limit
+    #[structopt(long = "--fps", default_value = "10")]
+    fps: u32,
 }
 
 fn run() -> Result<(), Error> {
@@ -194,9 +267,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
@@ -1,5 +1,3 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
@@ -38,3 +36,15 @@ fn puzzle2() {
         .is("219919")
         .unwrap();
 }
+
+#[test]
+fn zero_scale() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["--frames", ".", "--scale", "0"])
+        .stdin("|.\n.#")
+        .fails()
+        .and()
+        .stderr()
+        .contains("The scale must be at least 1 pixel per tile, not 0")
+        .unwrap();
+}
@@ -0,0 +1,124 @@
+#![allow(dead_code, unused_imports)]
+
+use failure::bail;
+use failure::Error;
+use rayon::prelude::*;
+use structopt::StructOpt;
+use strum_macros::EnumString;
+use tap::{TapOps, TapOptionOps, TapResultOps};
+
+use hashbrown::{HashMap, HashSet};
+use itertools::Itertools;
+use std::char;
+use std::cmp::{max, min};
+use std::fmt;
+use std::io::BufRead;
+use std::ops::*;
+use std::str;
+use std::str::FromStr;
+use std::{io, process};
+
+use aoc2018::similarity::*;
+
+type Input = Vec<String>;
+type Output = String;
+
+fn read_input() -> Result<Input, Error> {
+    let stdin = io::stdin();
+    let mut result = Vec::new();
+    for line in stdin.lock().lines() {
+        let line = line?.trim().to_owned();
+        if !line.is_empty() {
+            result.push(line)
+        }
+    }
+
+    Ok(result)
+}
+
+fn solve1(input: &mut Input, _options: &Opt) -> Result<Output, Error> {
+    let mut twos = 0;
+    let mut threes = 0;
+
+    for word in input.iter() {
+        let mut chars = HashMap::new();
+        for c in word.chars() {
+            *chars.entry(c).or_insert(0) += 1;
+        }
+        let mut doubles = 0;
+        let mut triples = 0;
+        for value in chars.values() {
+            match value {
+                2 => doubles += 1,
+                3 => triples += 1,
+                _ => (),
+            }
+        }
+        if doubles >= 1 {
+            twos += 1;
+        }
+        if triples >= 1 {
+            threes += 1;
+        }
+    }
+
+    Ok(format!("{}", twos * threes))
+}
+
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    if let Some((a, b)) = input
+        .iter()
+        .tuple_windows()
+        .find(|(a, b)| a.chars().count() != b.chars().count())
+    {
+        bail!("Box IDs \"{}\" and \"{}\" have different lengths", a, b);
+    }
+
+    let pairs = pairs_at_distance(input, options.distance);
+    if pairs.is_empty() {
+        bail!("No fabric pair found");
+    }
+    Ok(pairs
+        .into_iter()
+        .map(|(a, b)| common_chars(&input[a], &input[b]))
+        .join("\n"))
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "day2")]
+struct Opt {
+    /// Part to solve, either 1 or 2
+    #[structopt(short = "-p", long = "--part", default_value = "1")]
+    part: u8,
+    /// Number of differing characters between the box IDs of a fabric pair
+    #[structopt(short = "-d", long = "--distance", default_value = "1")]
+    distance: usize,
+}
+
+fn run() -> Result<(), Error> {
+    let mut input = read_input()?;
+
+    let options: Opt = Opt::from_args();
+
+    let output = if options.part == 1 {
+        solve1(&mut input, &options)?
+    } else {
+        solve2(&mut input, &options)?
+    };
+
+    println!("{}", output);
+    Ok(())
+}
+
+fn main() {
+    match run() {
+        Ok(()) => process::exit(0),
+        Err(error) => {
+            eprintln!("Error while solving problem: {}", error);
+            for cause in error.iter_causes() {
+                eprintln!("{}", cause)
+            }
+            process::exit(1)
+        }
+    }
+}
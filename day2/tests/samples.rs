@@ -0,0 +1,107 @@
+#[test]
+fn sample1_1() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "abcdef
+bababc
+abbcde
+abcccd
+aabcdd
+abcdee
+ababab
+",
+        )
+        .stdout()
+        .is("12")
+        .unwrap();
+}
+
+#[test]
+fn puzzle1() {
+    assert_cli::Assert::main_binary()
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("6000")
+        .unwrap();
+}
+
+#[test]
+fn sample2_1() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "abcde
+fghij
+klmno
+pqrst
+fguij
+axcye
+wvxyz
+",
+        )
+        .stdout()
+        .is("fgij")
+        .unwrap();
+}
+
+#[test]
+fn puzzle2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("pbykrmjmizwhxlqnasfgtycdv")
+        .unwrap();
+}
+
+#[test]
+fn all_pairs2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "abcde
+fghij
+fguij
+fghik
+",
+        )
+        .stdout()
+        .is("fgij
+fghi")
+        .unwrap();
+}
+
+#[test]
+fn distance2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--distance", "2"])
+        .stdin(
+            "abcde
+fghij
+klmno
+pqrst
+fguij
+axcye
+wvxyz
+",
+        )
+        .stdout()
+        .is("ace")
+        .unwrap();
+}
+
+#[test]
+fn unequal_lengths2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "abcde
+abcd
+",
+        )
+        .fails()
+        .and()
+        .stderr()
+        .contains("different lengths")
+        .unwrap();
+}
@@ -1,24 +0,0 @@
-use assert_cli;
-
-#[test]
-fn sample1() {
-    assert_cli::Assert::main_binary()
-        .stdin(
-            "#1 @ 1,3: 4x4
-#2 @ 3,1: 4x4
-#3 @ 5,5: 2x2
-",
-        )
-        .stdout()
-        .is("4")
-        .unwrap();
-}
-
-#[test]
-fn puzzle1() {
-    assert_cli::Assert::main_binary()
-        .stdin(include_str!("../data/puzzle1.in"))
-        .stdout()
-        .is("115304")
-        .unwrap();
-}
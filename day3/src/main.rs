@@ -4,6 +4,7 @@ use failure::bail;
 use failure::err_msg;
 use failure::Error;
 use rayon::prelude::*;
+use structopt::StructOpt;
 use strum_macros::EnumString;
 use tap::{TapOps, TapOptionOps, TapResultOps};
 
@@ -18,7 +19,9 @@ use std::str::FromStr;
 use std::{io, process};
 
 use aoc2018::input::*;
+use aoc2018::interval::*;
 use aoc2018::matrix::*;
+use aoc2018::position::*;
 
 type Input = Vec<Claim>;
 type Output = usize;
@@ -49,6 +52,13 @@ impl Claim {
     fn height(&self) -> usize {
         self.bottom - self.top + 1
     }
+
+    fn rect(&self) -> Rect {
+        Rect::new(
+            (self.left, self.top).into(),
+            (self.right, self.bottom).into(),
+        )
+    }
 }
 
 impl FromStr for Claim {
@@ -78,37 +88,61 @@ fn read_input() -> Result<Input, Error> {
     Ok(result)
 }
 
-fn solve(input: &mut Input) -> Result<Output, Error> {
-    const WIDTH: usize = 1000;
-    const HEIGHT: usize = 1000;
-
-    let mut claim_count = [[0; HEIGHT]; WIDTH];
+/// The ids of all pairs of overlapping claims, ordered by the position of the claims in the input.
+fn overlaps(claims: &[Claim]) -> Vec<(usize, usize)> {
+    let rects = claims.iter().map(Claim::rect).collect::<Vec<_>>();
+    overlapping_pairs(&rects)
+        .into_iter()
+        .map(|(a, b)| (claims[a].id, claims[b].id))
+        .collect()
+}
 
-    for claim in input.iter() {
-        for w in claim.left..=claim.right {
-            for h in claim.top..=claim.bottom {
-                claim_count[w][h] += 1;
-            }
-        }
-    }
+fn solve1(input: &mut Input) -> Result<Output, Error> {
+    let rects = input.iter().map(Claim::rect).collect::<Vec<_>>();
+    Ok(covered_area(&rects, 2) as usize)
+}
 
-    let mut result = 0;
-    for w in 0..WIDTH {
-        for h in 0..HEIGHT {
-            if claim_count[w][h] > 1 {
-                result += 1;
-            }
-        }
-    }
+fn solve2(input: &mut Input) -> Result<Output, Error> {
+    let overlapping = overlaps(input)
+        .into_iter()
+        .flat_map(|(a, b)| vec![a, b])
+        .collect::<HashSet<_>>();
+
+    input
+        .iter()
+        .map(|claim| claim.id)
+        .find(|id| !overlapping.contains(id))
+        .ok_or_else(|| err_msg("No non-overlapping claim found"))
+}
 
-    Ok(result)
+#[derive(StructOpt, Debug)]
+#[structopt(name = "day3")]
+struct Opt {
+    /// Part to solve, either 1 or 2
+    #[structopt(short = "-p", long = "--part", default_value = "1")]
+    part: u8,
+    /// Also print each pair of overlapping claims
+    #[structopt(short = "-o", long = "--overlaps")]
+    overlaps: bool,
 }
 
 fn run() -> Result<(), Error> {
     let mut input = read_input()?;
-    let output = solve(&mut input)?;
+
+    let options: Opt = Opt::from_args();
+
+    let output = if options.part == 1 {
+        solve1(&mut input)?
+    } else {
+        solve2(&mut input)?
+    };
 
     println!("{}", output);
+    if options.overlaps {
+        for (a, b) in overlaps(&input) {
+            println!("#{} overlaps #{}", a, b);
+        }
+    }
     Ok(())
 }
 
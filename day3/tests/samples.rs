@@ -0,0 +1,82 @@
+#[test]
+fn sample1_1() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+",
+        )
+        .stdout()
+        .is("4")
+        .unwrap();
+}
+
+#[test]
+fn puzzle1() {
+    assert_cli::Assert::main_binary()
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("115304")
+        .unwrap();
+}
+
+#[test]
+fn large_claims() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["--overlaps"])
+        .stdin(
+            "#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+#4 @ 100000,200000: 5x5
+#5 @ 100002,200003: 3000x3000
+",
+        )
+        .stdout()
+        .is("10
+#1 overlaps #2
+#4 overlaps #5")
+        .unwrap();
+}
+
+#[test]
+fn sample2_1() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+",
+        )
+        .stdout()
+        .is("3")
+        .unwrap();
+}
+
+#[test]
+fn puzzle2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("275")
+        .unwrap();
+}
+
+#[test]
+fn sample2_overlaps() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--overlaps"])
+        .stdin(
+            "#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+",
+        )
+        .stdout()
+        .is("3
+#1 overlaps #2")
+        .unwrap();
+}
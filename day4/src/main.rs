@@ -0,0 +1,331 @@
+#![allow(dead_code, unused_imports, clippy::needless_range_loop)]
+
+use failure::bail;
+use failure::err_msg;
+use failure::format_err;
+use failure::Error;
+use rayon::prelude::*;
+use structopt::StructOpt;
+use strum_macros::EnumString;
+use tap::{TapOps, TapOptionOps, TapResultOps};
+
+use hashbrown::{HashMap, HashSet};
+use std::char;
+use std::cmp::{max, min, Reverse};
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io::BufRead;
+use std::ops::*;
+use std::str;
+use std::str::FromStr;
+use std::{io, process};
+
+use aoc2018::input::*;
+use aoc2018::matrix::*;
+use std::collections::BTreeSet;
+
+type Input = Log;
+type Output = usize;
+
+/// A point in time with minute resolution.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+struct Timestamp {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
+
+impl Timestamp {
+    /// Minutes since 0000-03-01 00:00 in the proleptic Gregorian calendar.
+    fn minutes(&self) -> i64 {
+        let year = if self.month <= 2 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from((self.month + 9) % 12);
+        let day_of_year = (153 * month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era;
+        (days * 24 + i64::from(self.hour)) * 60 + i64::from(self.minute)
+    }
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let parts = s.split(&['-', ' ', ':'][..]).collect::<Vec<_>>();
+        if parts.len() != 5 {
+            bail!("Could not parse timestamp \"{}\"", s);
+        }
+        let timestamp = Timestamp {
+            year: parts[0].parse()?,
+            month: parts[1].parse()?,
+            day: parts[2].parse()?,
+            hour: parts[3].parse()?,
+            minute: parts[4].parse()?,
+        };
+        if timestamp.month < 1
+            || timestamp.month > 12
+            || timestamp.day < 1
+            || timestamp.day > days_in_month(timestamp.year, timestamp.month)
+            || timestamp.hour > 23
+            || timestamp.minute > 59
+        {
+            bail!("Timestamp \"{}\" is not a valid time", s);
+        }
+        Ok(timestamp)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+enum Event {
+    ShiftStart(usize),
+    FallsAsleep,
+    WakesUp,
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+struct Entry {
+    time: Timestamp,
+    event: Event,
+}
+
+impl FromStr for Entry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let s = s.trim();
+        let close = s
+            .find(']')
+            .filter(|_| s.starts_with('['))
+            .ok_or_else(|| format_err!("Missing timestamp in \"{}\"", s))?;
+        let time = s[1..close].parse()?;
+        let text = s[close + 1..].trim();
+        let event = if text == "falls asleep" {
+            Event::FallsAsleep
+        } else if text == "wakes up" {
+            Event::WakesUp
+        } else if text.starts_with("Guard #") && text.ends_with(" begins shift") {
+            let guard = &text["Guard #".len()..text.len() - " begins shift".len()];
+            Event::ShiftStart(guard.parse()?)
+        } else {
+            bail!("Could not parse line: \"{}\"", s);
+        };
+        Ok(Entry { time, event })
+    }
+}
+
+/// A guard sleeping from `start` up to but not including `end`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+struct Sleep {
+    guard: usize,
+    start: Timestamp,
+    end: Timestamp,
+}
+
+impl Sleep {
+    fn duration(&self) -> usize {
+        (self.end.minutes() - self.start.minutes()) as usize
+    }
+
+    /// The minutes of the midnight hour that the guard is asleep, once for each night.
+    fn midnight_minutes(&self) -> impl Iterator<Item = usize> {
+        (self.start.minutes()..self.end.minutes())
+            .map(|minute| minute.rem_euclid(24 * 60) as usize)
+            .filter(|&minute| minute < 60)
+    }
+}
+
+/// All the sleeps of the guards, from a validated log.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Log {
+    sleeps: Vec<Sleep>,
+}
+
+impl Log {
+    fn new(mut entries: Vec<Entry>) -> Result<Log, Error> {
+        entries.sort();
+        for pair in entries.windows(2) {
+            if pair[0].time == pair[1].time {
+                bail!("Several entries at {}", pair[0].time);
+            }
+        }
+
+        let mut sleeps = Vec::new();
+        let mut guard = None;
+        let mut asleep = None;
+        for entry in entries {
+            match entry.event {
+                Event::ShiftStart(next) => {
+                    if let (Some(current), Some(_)) = (guard, asleep) {
+                        bail!(
+                            "Guard #{} begins shift at {} while guard #{} is asleep",
+                            next,
+                            entry.time,
+                            current
+                        );
+                    }
+                    guard = Some(next);
+                }
+                Event::FallsAsleep => match (guard, asleep) {
+                    (None, _) => bail!("Someone falls asleep at {} before any shift", entry.time),
+                    (Some(current), Some(start)) => bail!(
+                        "Guard #{} falls asleep at {} while asleep since {}",
+                        current,
+                        entry.time,
+                        start
+                    ),
+                    (Some(_), None) => asleep = Some(entry.time),
+                },
+                Event::WakesUp => match (guard, asleep) {
+                    (Some(current), Some(start)) => {
+                        sleeps.push(Sleep {
+                            guard: current,
+                            start,
+                            end: entry.time,
+                        });
+                        asleep = None;
+                    }
+                    _ => bail!("Someone wakes up at {} without falling asleep", entry.time),
+                },
+            }
+        }
+        if let (Some(current), Some(start)) = (guard, asleep) {
+            bail!(
+                "Guard #{} falls asleep at {} and never wakes up",
+                current,
+                start
+            );
+        }
+
+        Ok(Log { sleeps })
+    }
+
+    /// For each guard, the number of nights asleep at each minute of the midnight hour.
+    fn histograms(&self) -> HashMap<usize, [usize; 60]> {
+        let mut histograms = HashMap::new();
+        for sleep in self.sleeps.iter() {
+            let histogram = histograms.entry(sleep.guard).or_insert([0; 60]);
+            for minute in sleep.midnight_minutes() {
+                histogram[minute] += 1;
+            }
+        }
+        histograms
+    }
+
+    /// For each guard, the total number of minutes asleep.
+    fn total_sleep(&self) -> HashMap<usize, usize> {
+        let mut total_sleep = HashMap::new();
+        for sleep in self.sleeps.iter() {
+            *total_sleep.entry(sleep.guard).or_insert(0) += sleep.duration();
+        }
+        total_sleep
+    }
+}
+
+fn read_input() -> Result<Input, Error> {
+    let stdin = io::stdin();
+    let mut entries = Vec::new();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            entries.push(line.parse()?);
+        }
+    }
+
+    Log::new(entries)
+}
+
+/// The most common minute in a histogram and its count, preferring the earliest minute.
+fn most_common_minute(histogram: &[usize; 60]) -> (usize, usize) {
+    histogram
+        .iter()
+        .enumerate()
+        .max_by_key(|&(minute, &count)| (count, Reverse(minute)))
+        .map(|(minute, &count)| (minute, count))
+        .unwrap()
+}
+
+fn solve1(input: &mut Input) -> Result<Output, Error> {
+    let (guard, _) = input
+        .total_sleep()
+        .into_iter()
+        .max_by_key(|&(guard, total_sleep)| (total_sleep, Reverse(guard)))
+        .ok_or_else(|| err_msg("No guard ever sleeps"))?;
+    let (minute, _) = most_common_minute(&input.histograms()[&guard]);
+
+    Ok(guard * minute)
+}
+
+fn solve2(input: &mut Input) -> Result<Output, Error> {
+    let (guard, (minute, _)) = input
+        .histograms()
+        .iter()
+        .map(|(&guard, histogram)| (guard, most_common_minute(histogram)))
+        .max_by_key(|&(guard, (minute, count))| (count, Reverse(guard), Reverse(minute)))
+        .ok_or_else(|| err_msg("No guard ever sleeps"))?;
+
+    Ok(guard * minute)
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "day4")]
+struct Opt {
+    /// Part to solve, either 1 or 2
+    #[structopt(short = "-p", long = "--part", default_value = "1")]
+    part: u8,
+}
+
+fn run() -> Result<(), Error> {
+    let mut input = read_input()?;
+
+    let options: Opt = Opt::from_args();
+
+    let output = if options.part == 1 {
+        solve1(&mut input)?
+    } else {
+        solve2(&mut input)?
+    };
+
+    println!("{}", output);
+    Ok(())
+}
+
+fn main() {
+    match run() {
+        Ok(()) => process::exit(0),
+        Err(error) => {
+            eprintln!("Error while solving problem: {}", error);
+            for cause in error.iter_causes() {
+                eprintln!("{}", cause)
+            }
+            process::exit(1)
+        }
+    }
+}
@@ -0,0 +1,190 @@
+#[test]
+fn sample1_1() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+",
+        )
+        .stdout()
+        .is("240")
+        .unwrap();
+}
+
+#[test]
+fn sample1_unordered() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-02 00:40] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:55] wakes up
+",
+        )
+        .stdout()
+        .is("240")
+        .unwrap();
+}
+
+#[test]
+fn puzzle1() {
+    assert_cli::Assert::main_binary()
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("140932")
+        .unwrap();
+}
+
+#[test]
+fn sample2_1() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+",
+        )
+        .stdout()
+        .is("4455")
+        .unwrap();
+}
+
+#[test]
+fn sample2_unordered() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-02 00:40] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:55] wakes up
+",
+        )
+        .stdout()
+        .is("4455")
+        .unwrap();
+}
+
+#[test]
+fn puzzle2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("51232")
+        .unwrap();
+}
+
+#[test]
+fn sleep_across_midnight() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(
+            "[1518-02-28 23:50] Guard #10 begins shift
+[1518-02-28 23:55] falls asleep
+[1518-03-01 00:10] wakes up
+[1518-03-02 00:01] Guard #10 begins shift
+[1518-03-02 00:05] falls asleep
+[1518-03-02 00:20] wakes up
+",
+        )
+        .stdout()
+        .is("50")
+        .unwrap();
+}
+
+#[test]
+fn wake_without_sleep() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:25] wakes up
+",
+        )
+        .fails()
+        .and()
+        .stderr()
+        .contains("wakes up at 1518-11-01 00:25 without falling asleep")
+        .unwrap();
+}
+
+#[test]
+fn overlapping_shifts() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:10] Guard #99 begins shift
+[1518-11-01 00:25] wakes up
+",
+        )
+        .fails()
+        .and()
+        .stderr()
+        .contains("Guard #99 begins shift at 1518-11-01 00:10 while guard #10 is asleep")
+        .unwrap();
+}
+
+#[test]
+fn invalid_timestamp() {
+    assert_cli::Assert::main_binary()
+        .stdin("[1518-02-29 00:00] Guard #10 begins shift\n")
+        .fails()
+        .unwrap();
+}
@@ -0,0 +1,188 @@
+#![allow(
+    dead_code,
+    unused_imports,
+    clippy::needless_range_loop,
+    clippy::ptr_arg,
+    clippy::char_lit_as_u8
+)]
+
+use failure::bail;
+use failure::err_msg;
+use failure::Error;
+use rayon::prelude::*;
+use structopt::StructOpt;
+use strum_macros::EnumString;
+use tap::{TapOps, TapOptionOps, TapResultOps};
+
+use hashbrown::{HashMap, HashSet};
+use std::char;
+use std::cmp::{max, min};
+use std::fmt;
+use std::io::BufRead;
+use std::iter::*;
+use std::ops::*;
+use std::str;
+use std::str::FromStr;
+use std::{io, process};
+
+use aoc2018::input::*;
+use aoc2018::matrix::*;
+use std::collections::BTreeSet;
+
+type Input = Vec<char>;
+type Output = Reduction;
+
+/// The length of a fully reacted polymer, and the unit type removed before reacting, if any.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Reduction {
+    length: usize,
+    removed: Option<char>,
+}
+
+fn read_input() -> Result<Input, Error> {
+    let stdin = io::stdin();
+    let mut lines = Vec::new();
+    for line in stdin.lock().lines() {
+        let line = line?.trim().to_owned();
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    if lines.len() != 1 {
+        bail!("Expected a single polymer, found {} lines", lines.len());
+    }
+
+    Ok(lines[0].chars().collect())
+}
+
+fn polarity_pair(ch1: char, ch2: char) -> bool {
+    ch1 != ch2 && unit_type(ch1) == unit_type(ch2)
+}
+
+/// The type of a unit, ignoring its polarity.
+fn unit_type(unit: char) -> char {
+    unit.to_lowercase().next().unwrap_or(unit)
+}
+
+/// Reacts polymers, with adjacent units annihilating each other when `reacts` holds for them.
+struct Reactor<F>
+where
+    F: Fn(char, char) -> bool,
+{
+    reacts: F,
+}
+
+impl<F> Reactor<F>
+where
+    F: Fn(char, char) -> bool + Sync,
+{
+    fn new(reacts: F) -> Reactor<F> {
+        Reactor { reacts }
+    }
+
+    /// Fully react a polymer in a single pass.
+    ///
+    /// Units that survive are kept on a stack, so a reaction that uncovers a new adjacent pair is
+    /// found by comparing the next unit with the new top of the stack.
+    fn react<I>(&self, polymer: I) -> Vec<char>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut result = Vec::new();
+        for unit in polymer {
+            match result.last() {
+                Some(&last) if (self.reacts)(last, unit) => {
+                    result.pop();
+                }
+                _ => result.push(unit),
+            }
+        }
+        result
+    }
+
+    /// The unit type whose removal gives the shortest fully reacted polymer.
+    ///
+    /// Removing units never enables reactions inside a part that has already fully reacted, so
+    /// each removal starts from the reacted polymer. Ties go to the first unit type in order.
+    fn best_removal(&self, polymer: &[char]) -> Reduction {
+        let reacted = self.react(polymer.iter().cloned());
+        let unit_types = reacted
+            .iter()
+            .map(|&unit| unit_type(unit))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        unit_types
+            .into_par_iter()
+            .map(|removed| {
+                let remaining = reacted
+                    .iter()
+                    .cloned()
+                    .filter(|&unit| unit_type(unit) != removed);
+                Reduction {
+                    length: self.react(remaining).len(),
+                    removed: Some(removed),
+                }
+            })
+            .min_by_key(|reduction| (reduction.length, reduction.removed))
+            .unwrap_or(Reduction {
+                length: 0,
+                removed: None,
+            })
+    }
+}
+
+fn solve1(input: &mut Input) -> Result<Output, Error> {
+    let reactor = Reactor::new(polarity_pair);
+    Ok(Reduction {
+        length: reactor.react(input.iter().cloned()).len(),
+        removed: None,
+    })
+}
+
+fn solve2(input: &mut Input) -> Result<Output, Error> {
+    let reactor = Reactor::new(polarity_pair);
+    Ok(reactor.best_removal(input))
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "day5")]
+struct Opt {
+    /// Part to solve, either 1 or 2
+    #[structopt(short = "-p", long = "--part", default_value = "1")]
+    part: u8,
+    /// Also print the unit type that was removed
+    #[structopt(short = "-u", long = "--unit")]
+    unit: bool,
+}
+
+fn run() -> Result<(), Error> {
+    let mut input = read_input()?;
+
+    let options: Opt = Opt::from_args();
+
+    let output = if options.part == 1 {
+        solve1(&mut input)?
+    } else {
+        solve2(&mut input)?
+    };
+
+    match output.removed {
+        Some(unit) if options.unit => println!("{} {}", output.length, unit),
+        _ => println!("{}", output.length),
+    }
+    Ok(())
+}
+
+fn main() {
+    match run() {
+        Ok(()) => process::exit(0),
+        Err(error) => {
+            eprintln!("Error while solving problem: {}", error);
+            for cause in error.iter_causes() {
+                eprintln!("{}", cause)
+            }
+            process::exit(1)
+        }
+    }
+}
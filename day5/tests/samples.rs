@@ -0,0 +1,57 @@
+#[test]
+fn sample1_1() {
+    assert_cli::Assert::main_binary()
+        .stdin("dabAcCaCBAcCcaDA")
+        .stdout()
+        .is("10")
+        .unwrap();
+}
+
+#[test]
+fn puzzle1() {
+    assert_cli::Assert::main_binary()
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("11252")
+        .unwrap();
+}
+
+#[test]
+fn sample2_1() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin("dabAcCaCBAcCcaDA")
+        .stdout()
+        .is("4")
+        .unwrap();
+}
+
+#[test]
+fn puzzle2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2"])
+        .stdin(include_str!("../data/puzzle1.in"))
+        .stdout()
+        .is("6118")
+        .unwrap();
+}
+
+#[test]
+fn sample2_unit() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--unit"])
+        .stdin("dabAcCaCBAcCcaDA")
+        .stdout()
+        .is("4 c")
+        .unwrap();
+}
+
+#[test]
+fn fully_reacting2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--unit"])
+        .stdin("abBA")
+        .stdout()
+        .is("0")
+        .unwrap();
+}
@@ -28,148 +28,78 @@ use tap::{TapOps, TapOptionOps, TapResultOps};
 
 use aoc2018::input::*;
 use aoc2018::matrix::*;
+use aoc2018::position::*;
+use aoc2018::voronoi::Voronoi;
 use std::collections::BTreeSet;
 
-type Input = Vec<(usize, usize)>;
+type Input = Vec<Position>;
 type Output = usize;
 
-const PAD: usize = 2;
-
 fn read_input() -> Result<Input, Error> {
     let stdin = io::stdin();
     let mut result = Vec::new();
     for line in stdin.lock().lines() {
         let line = line?;
-        let numbers = get_numbers::<usize>(&line)?;
+        let numbers = get_numbers::<Scalar>(&line)?;
         if numbers.len() == 2 {
-            result.push((numbers[0] + PAD, numbers[1] + PAD));
+            result.push(Position::new(numbers[0], numbers[1]));
         }
     }
 
     Ok(result)
 }
 
-fn print_matrix(max_x: usize, max_y: usize, current: &Vec<Vec<usize>>) {
-    println!("Matrix:");
-    for y in 0..max_y {
-        for x in 0..max_x {
-            if current[x][y] == 0 {
-                print!("·");
-            } else {
-                let ch = (('a' as usize) + current[x][y] - 1) as u8 as char;
-                print!("{}", ch);
-            }
-        }
-        println!();
-    }
+fn solve1(input: &mut Input, _options: &Opt) -> Result<Output, Error> {
+    let voronoi = Voronoi::new(input).ok_or_else(|| err_msg("No coordinates given"))?;
+    let (_seed, size) = voronoi
+        .largest_bounded_region()
+        .ok_or_else(|| err_msg("All areas are infinite"))?;
+    Ok(size)
 }
 
-fn distance(x1: usize, y1: usize, x2: usize, y2: usize) -> usize {
-    ((x1 as i64 - x2 as i64).abs() + (y1 as i64 - y2 as i64).abs()) as usize
+/// The total distance from each coordinate along one axis to all the given coordinates, for all
+/// coordinates where it is less than `threshold`.
+///
+/// Outside the given coordinates the total grows by at least one per coordinate and step, which
+/// limits how far out to look.
+fn axis_totals(coordinates: &[i64], threshold: i64) -> Vec<i64> {
+    let margin = threshold / coordinates.len() as i64 + 1;
+    let low = coordinates.iter().min().unwrap() - margin;
+    let high = coordinates.iter().max().unwrap() + margin;
+    (low..=high)
+        .map(|c| coordinates.iter().map(|&other| (c - other).abs()).sum())
+        .filter(|&total| total < threshold)
+        .collect()
 }
 
-fn closest(markers: &Vec<(usize, (usize, usize))>, x: usize, y: usize) -> usize {
-    let distances = markers
+/// The number of positions with total distance to all coordinates less than `threshold`.
+///
+/// The Manhattan distance splits into a part for each axis, so the totals for each axis are
+/// computed separately, and pairs of them below the threshold are counted. This also counts
+/// positions outside the bounding box of the coordinates.
+fn safe_region_size(coordinates: &[Position], threshold: i64) -> usize {
+    if coordinates.is_empty() {
+        return 0;
+    }
+    let xs = coordinates
         .iter()
-        .map(|&(m, (mx, my))| (m, distance(mx, my, x, y)))
+        .map(|p| i64::from(p.x))
         .collect::<Vec<_>>();
-    let min_distance = distances.iter().map(|&(_m, d)| d).min().unwrap();
-    let all_min = distances
+    let ys = coordinates
         .iter()
-        .filter(|&(_m, d)| *d == min_distance)
+        .map(|p| i64::from(p.y))
         .collect::<Vec<_>>();
-    if all_min.len() == 1 {
-        all_min[0].0
-    } else {
-        0
-    }
-}
-
-fn make_matrix1(input: &mut Vec<(usize, usize)>, max_x: usize, max_y: usize) -> Vec<Vec<usize>> {
-    let mut current = vec![vec![0 as usize; max_y]; max_x];
-
-    let markers = input
-        .iter()
-        .enumerate()
-        .map(|(i, &(x, y))| (i + 1, (x, y)))
-        .collect();
-
-    for x in 0..max_x {
-        for y in 0..max_y {
-            current[x][y] = closest(&markers, x, y);
-        }
-    }
-
-    //    print_matrix(max_x, max_y, &current);
-
-    current
-}
-
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let max_x = input.iter().map(|&(a, _)| a).max().unwrap() + 1 + PAD;
-    let max_y = input.iter().map(|&(_, b)| b).max().unwrap() + 1 + PAD;
-
-    let current = make_matrix1(input, max_x, max_y);
-
-    let mut infinite = HashSet::new();
-    infinite.insert(0);
-    for x in 0..max_x {
-        infinite.insert(current[x][0]);
-        infinite.insert(current[x][max_y - 1]);
-    }
-    for y in 0..max_y {
-        infinite.insert(current[0][y]);
-        infinite.insert(current[max_x - 1][y]);
-    }
-
-    let non_infinite = current
-        .iter()
-        .flatten()
-        .filter(|v| !infinite.contains(v))
-        .cloned()
-        .collect::<Frequencies<usize>>();
-
-    //    println!("{:?}", infinite);
-    //    println!("{:?}", non_infinite);
-
-    Ok(non_infinite.most_frequent()[0].1 as usize)
-}
-
-fn make_matrix2(input: &Vec<(usize, usize)>, max_x: usize, max_y: usize) -> Vec<Vec<usize>> {
-    const MAX_DISTANCE: usize = 10000;
-
-    let mut current = vec![vec![0 as usize; max_y]; max_x];
-
-    let markers = input
+    let x_totals = axis_totals(&xs, threshold);
+    let mut y_totals = axis_totals(&ys, threshold);
+    y_totals.sort();
+    x_totals
         .iter()
-        .enumerate()
-        .map(|(i, &(x, y))| (i + 1, (x, y)))
-        .collect::<Vec<_>>();
-
-    for x in 0..max_x {
-        for y in 0..max_y {
-            let total_distance: usize = markers
-                .iter()
-                .map(|&(_m, (mx, my))| distance(x, y, mx, my))
-                .sum();
-            current[x][y] = if total_distance < MAX_DISTANCE { 1 } else { 0 }
-        }
-    }
-
-    //    print_matrix(max_x, max_y, &current);
-
-    current
+        .map(|&x_total| y_totals.partition_point(|&y_total| x_total + y_total < threshold))
+        .sum()
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
-    let max_x = input.iter().map(|&(a, _)| a).max().unwrap() + 1 + PAD;
-    let max_y = input.iter().map(|&(_, b)| b).max().unwrap() + 1 + PAD;
-
-    let map = make_matrix2(&input, max_x, max_y);
-
-    let result = map.iter().flatten().filter(|&&v| v == 1).count();
-
-    Ok(result)
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    Ok(safe_region_size(input, options.threshold))
 }
 
 #[derive(StructOpt, Debug)]
@@ -178,6 +108,9 @@ struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Total distance to all coordinates that a safe region must stay below, for part 2
+    #[structopt(short = "-t", long = "--threshold", default_value = "10000")]
+    threshold: i64,
 }
 
 fn run() -> Result<(), Error> {
@@ -186,9 +119,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
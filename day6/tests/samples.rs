@@ -1,5 +1,3 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
@@ -25,6 +23,23 @@ fn puzzle1() {
         .unwrap();
 }
 
+#[test]
+fn sample2_1() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--threshold", "32"])
+        .stdin(
+            "1, 1
+1, 6
+8, 3
+3, 4
+5, 5
+8, 9",
+        )
+        .stdout()
+        .is("16")
+        .unwrap();
+}
+
 #[test]
 fn puzzle2() {
     assert_cli::Assert::main_binary()
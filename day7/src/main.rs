@@ -2,8 +2,7 @@
     dead_code,
     unused_imports,
     clippy::needless_range_loop,
-    clippy::ptr_arg,
-    clippy::char_lit_as_u8
+    clippy::ptr_arg
 )]
 
 use std::char;
@@ -26,117 +25,66 @@ use structopt::StructOpt;
 use strum_macros::EnumString;
 use tap::{TapOps, TapOptionOps, TapResultOps};
 
+use aoc2018::dag::Dag;
 use aoc2018::input::*;
 use aoc2018::matrix::*;
 use std::collections::BTreeSet;
 
-type Input = Vec<(char, char)>;
+type Input = Dag<String>;
 type Output = String;
 
 fn read_input() -> Result<Input, Error> {
     let stdin = io::stdin();
-    let mut result = Vec::new();
+    let mut result = Dag::new();
     for line in stdin.lock().lines() {
         let line = line?;
         let line = line.trim();
-        if !line.is_empty() {
-            result.push((line.chars().nth(5).unwrap(), line.chars().nth(36).unwrap()));
+        if line.is_empty() {
+            continue;
+        }
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            ["Step", before, "must", "be", "finished", "before", "step", after, "can", "begin."] => {
+                result.add_edge(before.to_string(), after.to_string())
+            }
+            _ => bail!("Unrecognized instruction \"{}\"", line),
         }
     }
 
     Ok(result)
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let tasks: HashSet<char> = input.iter().flat_map(|&(a, b)| vec![a, b]).collect();
-    let mut done: HashSet<char> = HashSet::new();
-    let mut prereqs = HashMap::new();
-    for ch in tasks.iter() {
-        prereqs.insert(ch, HashSet::new());
-    }
-    let mut todo = tasks.clone();
-    for &(req, task) in input.iter() {
-        prereqs.get_mut(&task).unwrap().insert(req);
-    }
+fn solve1(input: &mut Input, _options: &Opt) -> Result<Output, Error> {
+    Ok(input.order_by(|step| step.clone())?.concat())
+}
 
-    let mut result: Vec<char> = Vec::new();
-    while !todo.is_empty() {
-        let ready = todo
-            .iter()
-            .cloned()
-            .filter(|c| prereqs[c].is_empty())
-            .collect::<Vec<_>>()
-            .tap(|v| v.sort());
-        let next = *ready.first().ok_or_else(|| err_msg("Ready is emtpy"))?;
-        result.push(next);
-        for prereq in prereqs.values_mut() {
-            prereq.remove(&next);
+/// The time to do a step named by a single letter, which is its position in the alphabet.
+fn step_time(step: &str) -> Result<u64, Error> {
+    let mut chars = step.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_ascii_alphabetic() => {
+            Ok(u64::from(letter.to_ascii_uppercase() as u8 - b'A' + 1))
         }
-        todo.remove(&next);
-        done.insert(next);
+        _ => bail!("Step \"{}\" is not a single letter and has no time", step),
     }
-
-    Ok(result.iter().collect())
 }
 
-fn solve2(input: &mut Input, workers: usize, base_time: u8) -> Result<Output, Error> {
-    let tasks: HashSet<char> = input.iter().flat_map(|&(a, b)| vec![a, b]).collect();
-    let mut done: HashSet<char> = HashSet::new();
-    let mut prereqs = HashMap::new();
-    for ch in tasks.iter() {
-        prereqs.insert(ch, HashSet::new());
-    }
-    let mut todo = tasks.clone();
-    for &(req, task) in input.iter() {
-        prereqs.get_mut(&task).unwrap().insert(req);
-    }
-
-    let mut result: Vec<char> = Vec::new();
-    let mut in_progress: HashMap<char, u8> = HashMap::new();
-    let mut time = 0;
-    while result.len() < tasks.len() {
-        for (_task, remaining) in in_progress.iter_mut() {
-            *remaining -= 1;
-        }
-        let completed = in_progress
-            .iter()
-            .filter(|&(_task, &remaining)| remaining == 0)
-            .map(|(&task, _remaining)| task)
-            .collect::<Vec<_>>()
-            .tap(|v| v.sort());
-        for task in completed.iter() {
-            in_progress.remove(task);
-            result.push(*task);
-            done.insert(*task);
-            for prereq in prereqs.values_mut() {
-                prereq.remove(task);
-            }
-        }
-
-        if in_progress.len() < workers {
-            let ready = todo
-                .iter()
-                .cloned()
-                .filter(|c| prereqs[c].is_empty())
-                .collect::<Vec<_>>()
-                .tap(|v| v.sort());
-            for task in ready {
-                let work_time = base_time + (task as u8 - 'A' as u8) + 1;
-                in_progress.insert(task, work_time);
-                todo.remove(&task);
-                if in_progress.len() == workers {
-                    break;
-                }
-            }
-        }
-        time += 1;
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    let times = input
+        .nodes()
+        .iter()
+        .map(|step| Ok((step.clone(), options.base_time + step_time(step)?)))
+        .collect::<Result<HashMap<_, _>, Error>>()?;
+    let schedule = input.schedule(options.workers, |step| step.clone(), |step| times[step])?;
+    if options.timeline {
+        Ok(format!("{}\n{}", schedule.makespan(), schedule))
+    } else {
+        Ok(format!("{}", schedule.makespan()))
     }
-
-    Ok(format!("{}", time - 1))
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "day6")]
+#[structopt(name = "day7")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
@@ -146,7 +94,10 @@ struct Opt {
     workers: usize,
     /// Base time
     #[structopt(short = "-b", long = "--base", default_value = "60")]
-    base_time: u8,
+    base_time: u64,
+    /// Show which worker did which step when, for part 2
+    #[structopt(short = "-t", long = "--timeline")]
+    timeline: bool,
 }
 
 fn run() -> Result<(), Error> {
@@ -155,9 +106,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input, options.workers, options.base_time)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
@@ -1,5 +1,3 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
@@ -53,3 +51,44 @@ fn puzzle2() {
         .is("877")
         .unwrap();
 }
+
+#[test]
+fn timeline2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "-w", "2", "-b", "0", "--timeline"])
+        .stdin(
+            "Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.",
+        )
+        .stdout()
+        .is("15
+Time  Worker 1  Worker 2
+   0     C         .
+   3     A         F
+   4     B         F
+   6     D         F
+   9     D         .
+  10     E         .
+  15     .         .")
+        .unwrap();
+}
+
+#[test]
+fn cycle1() {
+    assert_cli::Assert::main_binary()
+        .stdin(
+            "Step C must be finished before step A can begin.
+Step A must be finished before step B can begin.
+Step B must be finished before step C can begin.",
+        )
+        .fails()
+        .and()
+        .stderr()
+        .contains("Dependency cycle C -> A -> B -> C")
+        .unwrap();
+}
@@ -31,7 +31,7 @@ use aoc2018::matrix::*;
 use std::collections::BTreeSet;
 
 type Input = Vec<usize>;
-type Output = usize;
+type Output = String;
 
 fn read_input() -> Result<Input, Error> {
     let stdin = io::stdin();
@@ -46,64 +46,186 @@ fn read_input() -> Result<Input, Error> {
     Ok(result)
 }
 
-fn checksum(it: &mut impl Iterator<Item = usize>) -> usize {
-    let mut result = 0;
+/// A node in the license tree.
+///
+/// Parsing, folding, printing and dropping all use explicit stacks, so that very deep trees do
+/// not overflow the call stack.
+#[derive(Debug, Eq, PartialEq)]
+struct Node {
+    children: Vec<Node>,
+    metadata: Vec<usize>,
+}
+
+/// A node whose header has been read, waiting for its children to be parsed.
+struct Partial {
+    remaining_children: usize,
+    metadata_entries: usize,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn parse(numbers: &[usize]) -> Result<Node, Error> {
+        let mut position = 0;
+        let mut stack: Vec<Partial> = Vec::new();
+        loop {
+            let header = &numbers[position..numbers.len().min(position + 2)];
+            if header.len() < 2 {
+                bail!(
+                    "Input ends after {} numbers, in the header of a node at depth {}",
+                    numbers.len(),
+                    stack.len()
+                );
+            }
+            position += 2;
+            stack.push(Partial {
+                remaining_children: header[0],
+                metadata_entries: header[1],
+                children: Vec::new(),
+            });
 
-    let child_nodes = it.next().unwrap();
-    let checksum_entries = it.next().unwrap();
+            while stack.last().is_some_and(|top| top.remaining_children == 0) {
+                let partial = stack.pop().unwrap();
+                let end = position + partial.metadata_entries;
+                if end > numbers.len() {
+                    bail!(
+                        "Input ends after {} numbers, in the metadata of a node at depth {}",
+                        numbers.len(),
+                        stack.len()
+                    );
+                }
+                let node = Node {
+                    children: partial.children,
+                    metadata: numbers[position..end].to_vec(),
+                };
+                position = end;
 
-    for _child in 0..child_nodes {
-        result += checksum(it);
+                match stack.last_mut() {
+                    Some(parent) => {
+                        parent.children.push(node);
+                        parent.remaining_children -= 1;
+                    }
+                    None if position < numbers.len() => bail!(
+                        "Trailing data after the root node, {} numbers from position {}",
+                        numbers.len() - position,
+                        position
+                    ),
+                    None => return Ok(node),
+                }
+            }
+        }
     }
 
-    for _entry in 0..checksum_entries {
-        result += it.next().unwrap();
+    /// Combine the values of the children of each node with the node itself, from the leaves up.
+    fn fold<T, F>(&self, mut f: F) -> T
+    where
+        F: FnMut(&Node, Vec<T>) -> T,
+    {
+        let mut stack = vec![(self, 0)];
+        let mut values: Vec<T> = Vec::new();
+        while let Some(top) = stack.last_mut() {
+            let (node, visited) = *top;
+            if visited < node.children.len() {
+                top.1 += 1;
+                stack.push((&node.children[visited], 0));
+            } else {
+                stack.pop();
+                let children = values.split_off(values.len() - node.children.len());
+                values.push(f(node, children));
+            }
+        }
+        values.pop().unwrap()
     }
 
-    result
-}
+    /// The sum of all metadata entries in the tree.
+    fn checksum(&self) -> usize {
+        self.fold(|node, children: Vec<usize>| {
+            children.iter().sum::<usize>() + node.metadata.iter().sum::<usize>()
+        })
+    }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let mut iter = input.iter().cloned();
-    Ok(checksum(&mut iter))
+    /// The sum of the metadata for a leaf, otherwise the sum of the values of the children
+    /// referenced by the metadata, counting from 1 and ignoring references to missing children.
+    fn value(&self) -> usize {
+        self.fold(|node, children: Vec<usize>| {
+            if children.is_empty() {
+                node.metadata.iter().sum()
+            } else {
+                node.metadata
+                    .iter()
+                    .filter_map(|&entry| children.get(entry.checked_sub(1)?))
+                    .sum()
+            }
+        })
+    }
 }
 
-fn node_value(it: &mut impl Iterator<Item = usize>) -> usize {
-    let child_nodes = it.next().unwrap();
-    let checksum_entries = it.next().unwrap();
-
-    let mut child_values = Vec::new();
-    for _child in 0..child_nodes {
-        child_values.push(node_value(it));
+impl Drop for Node {
+    fn drop(&mut self) {
+        let mut pending = std::mem::take(&mut self.children);
+        while let Some(mut node) = pending.pop() {
+            pending.append(&mut node.children);
+        }
     }
+}
 
-    let mut result = 0;
-    for _entry_index in 0..checksum_entries {
-        let entry = it.next().unwrap();
-        if child_nodes > 0 {
-            result += if entry <= child_nodes {
-                child_values[entry - 1]
-            } else {
-                0
-            };
-        } else {
-            result += entry;
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        // Each node with the prefix of its own line and the prefix of the lines of its children.
+        let mut stack = vec![(self, String::new(), String::new())];
+        let mut first = true;
+        while let Some((node, prefix, child_prefix)) = stack.pop() {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "{}{:?}", prefix, node.metadata)?;
+            for (index, child) in node.children.iter().enumerate().rev() {
+                let (branch, continuation) = if index + 1 == node.children.len() {
+                    ("└── ", "    ")
+                } else {
+                    ("├── ", "│   ")
+                };
+                stack.push((
+                    child,
+                    format!("{}{}", child_prefix, branch),
+                    format!("{}{}", child_prefix, continuation),
+                ));
+            }
         }
+        Ok(())
+    }
+}
+
+fn solve(
+    input: &mut Input,
+    options: &Opt,
+    metric: impl Fn(&Node) -> usize,
+) -> Result<Output, Error> {
+    let tree = Node::parse(input)?;
+    if options.tree {
+        Ok(format!("{}\n{}", metric(&tree), tree))
+    } else {
+        Ok(format!("{}", metric(&tree)))
     }
+}
 
-    result
+fn solve1(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    solve(input, options, Node::checksum)
 }
-fn solve2(input: &mut Input) -> Result<Output, Error> {
-    let mut iter = input.iter().cloned();
-    Ok(node_value(&mut iter))
+
+fn solve2(input: &mut Input, options: &Opt) -> Result<Output, Error> {
+    solve(input, options, Node::value)
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "day6")]
+#[structopt(name = "day8")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
     part: u8,
+    /// Print the tree after the answer
+    #[structopt(short = "-t", long = "--tree")]
+    tree: bool,
 }
 
 fn run() -> Result<(), Error> {
@@ -112,9 +234,9 @@ fn run() -> Result<(), Error> {
     let options: Opt = Opt::from_args();
 
     let output = if options.part == 1 {
-        solve1(&mut input)?
+        solve1(&mut input, &options)?
     } else {
-        solve2(&mut input)?
+        solve2(&mut input, &options)?
     };
 
     println!("{}", output);
@@ -133,3 +255,23 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deep_tree() -> Result<(), Error> {
+        let depth = 200_000;
+        let mut numbers = Vec::with_capacity(3 * depth);
+        for _ in 1..depth {
+            numbers.extend_from_slice(&[1, 1]);
+        }
+        numbers.extend_from_slice(&[0, 1, 7]);
+        numbers.extend(std::iter::repeat_n(1, depth - 1));
+        let tree = Node::parse(&numbers)?;
+        assert_eq!(tree.checksum(), 7 + depth - 1);
+        assert_eq!(tree.value(), 7);
+        Ok(())
+    }
+}
@@ -1,5 +1,3 @@
-use assert_cli;
-
 #[test]
 fn sample1_1() {
     assert_cli::Assert::main_binary()
@@ -37,3 +35,39 @@ fn puzzle2() {
         .is("23960")
         .unwrap();
 }
+
+#[test]
+fn tree2() {
+    assert_cli::Assert::main_binary()
+        .with_args(&["-p", "2", "--tree"])
+        .stdin("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2")
+        .stdout()
+        .is("66
+[1, 1, 2]
+├── [10, 11, 12]
+└── [2]
+    └── [99]")
+        .unwrap();
+}
+
+#[test]
+fn truncated1() {
+    assert_cli::Assert::main_binary()
+        .stdin("2 3 0 3 10 11 12 1 1 0")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Input ends after 10 numbers, in the header of a node at depth 2")
+        .unwrap();
+}
+
+#[test]
+fn trailing1() {
+    assert_cli::Assert::main_binary()
+        .stdin("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2 5 6")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Trailing data after the root node")
+        .unwrap();
+}
@@ -29,6 +29,7 @@ use tap::{TapOps, TapOptionOps, TapResultOps};
 
 use aoc2018::input::*;
 use aoc2018::matrix::*;
+use aoc2018::ring::Ring;
 use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -62,7 +63,8 @@ fn step(pos: usize, steps: i32, marbles: usize) -> usize {
     }
 }
 
-fn play(players: usize, max_score: usize) -> Vec<usize> {
+/// Play the game with the marbles in a `Vec`, which is quadratic but simple to check.
+fn play_naive(players: usize, max_score: usize) -> Vec<usize> {
     let mut scores = vec![0; players];
     let mut marbles = vec![0];
     let mut pos = 0;
@@ -84,15 +86,6 @@ fn play(players: usize, max_score: usize) -> Vec<usize> {
     scores
 }
 
-fn solve1(input: &mut Input) -> Result<Output, Error> {
-    let (players, max_score) = *input;
-    let scores = play(players, max_score);
-    scores
-        .into_iter()
-        .max()
-        .ok_or_else(|| err_msg("No scores?"))
-}
-
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 struct Marbles {
     head: Vec<usize>,
@@ -189,6 +182,8 @@ impl Display for Marbles {
     }
 }
 
+/// Play the game with the marbles on two stacks, which moves all marbles over whenever a step
+/// empties one of them.
 fn play2(players: usize, max_score: usize) -> Vec<usize> {
     let mut scores = vec![0; players];
     let mut marbles = Marbles::initial();
@@ -208,17 +203,44 @@ fn play2(players: usize, max_score: usize) -> Vec<usize> {
     scores
 }
 
-fn solve2(input: &mut Input) -> Result<Output, Error> {
-    let (players, max_score) = *input;
-    let scores = play2(players, 100 * max_score);
+fn play(players: usize, max_score: usize) -> Vec<usize> {
+    let mut scores = vec![0; players];
+    let mut marbles = Ring::new();
+    marbles.insert(0);
+    let mut player = 0;
+    for marble in 1..=max_score {
+        if marble % 23 == 0 {
+            marbles.rotate(-7);
+            scores[player] += marble + marbles.remove().unwrap();
+        } else {
+            marbles.rotate(1);
+            marbles.insert(marble);
+        }
+        player = (player + 1) % players;
+    }
+
     scores
+}
+
+fn high_score(players: usize, max_score: usize) -> Result<Output, Error> {
+    play(players, max_score)
         .into_iter()
         .max()
-        .ok_or_else(|| err_msg("No scores?"))
+        .ok_or_else(|| err_msg("No players"))
+}
+
+fn solve1(input: &mut Input) -> Result<Output, Error> {
+    let (players, max_score) = *input;
+    high_score(players, max_score)
+}
+
+fn solve2(input: &mut Input) -> Result<Output, Error> {
+    let (players, max_score) = *input;
+    high_score(players, 100 * max_score)
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "day6")]
+#[structopt(name = "day9")]
 struct Opt {
     /// Part to solve, either 1 or 2
     #[structopt(short = "-p", long = "--part", default_value = "1")]
@@ -252,3 +274,27 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_implementations_agree() {
+        let samples = [
+            (9, 25, 32),
+            (10, 1618, 8317),
+            (13, 7999, 146_373),
+            (17, 1104, 2764),
+            (21, 6111, 54718),
+            (30, 5807, 37305),
+            (9, 2500, 22563),
+        ];
+        for &(players, last_marble, expected) in samples.iter() {
+            let scores = play(players, last_marble);
+            assert_eq!(scores.iter().max(), Some(&expected));
+            assert_eq!(scores, play_naive(players, last_marble));
+            assert_eq!(scores, play2(players, last_marble));
+        }
+    }
+}